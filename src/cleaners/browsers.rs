@@ -0,0 +1,161 @@
+use anyhow::Result;
+use console::style;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::Removal;
+use crate::utils::{app_support_home, cache_home, calculate_directory_size, format_size};
+
+/// Per-profile directories Chromium-based browsers rebuild on demand. Cookies,
+/// history, extensions and the rest of the profile are never touched.
+const CHROMIUM_CACHE_DIRS: &[&str] = &["Cache", "Code Cache", "GPUCache", "Service Worker/CacheStorage"];
+
+/// Per-profile cache directories of Firefox, below its cache root.
+const FIREFOX_CACHE_DIRS: &[&str] = &["cache2", "startupCache"];
+
+/// Chromium-based browsers as (name, Linux directory, macOS directory), relative
+/// to the config/cache home on Linux and Application Support/Caches on macOS.
+const CHROMIUM_BROWSERS: &[(&str, &str, &str)] = &[
+    ("Chrome", "google-chrome", "Google/Chrome"),
+    ("Chromium", "chromium", "Chromium"),
+    ("Brave", "BraveSoftware/Brave-Browser", "BraveSoftware/Brave-Browser"),
+    ("Edge", "microsoft-edge", "Microsoft Edge"),
+    ("Vivaldi", "vivaldi", "Vivaldi"),
+];
+
+/// Cleans the cache directories of every profile of Chrome, Chromium, Brave,
+/// Edge, Vivaldi and Firefox, plus Safari's cache on macOS.
+pub async fn cleanup_browser_caches(dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+    let mut found = false;
+
+    for (browser, removals) in find_browser_caches() {
+        found = true;
+        let total: u64 = removals.iter().map(|r| r.size).sum();
+        println!("  {} {}: {}", style("🌐").cyan(), browser, format_size(total));
+        total_freed += super::remove_with_confirmation(
+            &format!("{} caches (close {} first)", browser, browser),
+            &removals,
+            dry_run,
+            yes,
+        )?;
+    }
+
+    if !found {
+        println!("  {} No browser caches found", style("ℹ").blue());
+    }
+
+    Ok(total_freed)
+}
+
+/// Cache directories per browser, skipping browsers without any.
+fn find_browser_caches() -> Vec<(&'static str, Vec<Removal>)> {
+    let mut browsers = Vec::new();
+
+    for (name, linux_dir, macos_dir) in CHROMIUM_BROWSERS {
+        let dir = if cfg!(target_os = "macos") { macos_dir } else { linux_dir };
+        let mut removals = Vec::new();
+
+        // Profile data lives under the config directory, but Chromium moves the
+        // HTTP cache of each profile under the user cache directory
+        for root in [app_support_home().join(dir), cache_home().join(dir)] {
+            for profile in chromium_profiles(&root) {
+                removals.extend(cache_removals(&profile, CHROMIUM_CACHE_DIRS));
+            }
+        }
+
+        if !removals.is_empty() {
+            browsers.push((*name, removals));
+        }
+    }
+
+    let firefox: Vec<Removal> = firefox_profiles()
+        .iter()
+        .flat_map(|profile| {
+            // Older releases and custom profile locations keep cache2 inside the profile itself
+            let mut removals = cache_removals(&firefox_profile_root().join(profile), &["cache2"]);
+            if profile.is_relative() {
+                removals.extend(cache_removals(&firefox_cache_root().join(profile), FIREFOX_CACHE_DIRS));
+            }
+            removals
+        })
+        .collect();
+    if !firefox.is_empty() {
+        browsers.push(("Firefox", firefox));
+    }
+
+    if cfg!(target_os = "macos") {
+        let safari = cache_removals(&cache_home(), &["com.apple.Safari"]);
+        if !safari.is_empty() {
+            browsers.push(("Safari", safari));
+        }
+    }
+
+    browsers
+}
+
+/// Subdirectories of a Chromium user data or cache directory that hold a
+/// profile: `Default`, `Profile 1`, `Guest Profile` and so on.
+fn chromium_profiles(root: &Path) -> Vec<PathBuf> {
+    fs::read_dir(root)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_dir()
+                && (path.join("Preferences").exists() || CHROMIUM_CACHE_DIRS.iter().any(|dir| path.join(dir).exists()))
+        })
+        .collect()
+}
+
+/// Profile paths from Firefox's `profiles.ini`, relative to the profile root.
+/// Absolute paths (`IsRelative=0`) are returned as-is.
+fn firefox_profiles() -> Vec<PathBuf> {
+    let Ok(contents) = fs::read_to_string(firefox_profile_root().join("profiles.ini")) else {
+        return Vec::new();
+    };
+
+    let mut profiles = Vec::new();
+    let mut in_profile = false;
+    for line in contents.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_profile = line.starts_with("[Profile");
+        } else if let Some(path) = line.strip_prefix("Path=").filter(|_| in_profile) {
+            profiles.push(PathBuf::from(path));
+        }
+    }
+
+    profiles
+}
+
+fn cache_removals(profile: &Path, dirs: &[&str]) -> Vec<Removal> {
+    dirs.iter()
+        .map(|dir| profile.join(dir))
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let size = calculate_directory_size(&path).unwrap_or(0);
+            (size > 0).then(|| Removal {
+                description: path.display().to_string(),
+                path,
+                size,
+            })
+        })
+        .collect()
+}
+
+fn firefox_profile_root() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        app_support_home().join("Firefox")
+    } else {
+        PathBuf::from(shellexpand::tilde("~/.mozilla/firefox").as_ref())
+    }
+}
+
+fn firefox_cache_root() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        cache_home().join("Firefox")
+    } else {
+        cache_home().join("mozilla/firefox")
+    }
+}
@@ -5,155 +5,54 @@ use std::env;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
+use std::time::{Duration, SystemTime};
 use walkdir::WalkDir;
 
-fn calculate_directory_size(path: &Path) -> Result<u64> {
-    let mut total_size = 0;
-    
-    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
-        if let Ok(metadata) = entry.metadata() {
-            if metadata.is_file() {
-                total_size += metadata.len();
-            }
-        }
-    }
-    
-    Ok(total_size)
-}
+use crate::utils::calculate_directory_size;
+
+/// Cache directories under `$HOME` that are cleaned by removing their contents,
+/// keyed by profile target name.
+const DIRECTORY_CACHES: &[(&str, &str, &str)] = &[
+    ("homebrew", "Library/Caches/Homebrew", "Homebrew cache"),
+    ("pip", "Library/Caches/pip", "pip cache"),
+    ("composer", "Library/Caches/composer", "Composer cache"),
+    ("node-gyp", "Library/Caches/node-gyp", "node-gyp cache"),
+    ("playwright", "Library/Caches/ms-playwright", "Playwright cache"),
+];
 
 pub async fn cleanup_all_caches(dry_run: bool, yes: bool) -> Result<()> {
     println!("{}", style("🗂️  Cleaning All Cache Directories...").bold().cyan());
+    println!();
     
-    let mut total_freed = 0u64;
-    
-    // Run all cleaners individually
-    
-    println!("\n{} {}...", style("🧹").cyan(), "Homebrew Cache");
-    match cleanup_homebrew(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
-    }
-    
-    println!("\n{} {}...", style("🧹").cyan(), "pip Cache");
-    match cleanup_pip(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
-    }
-    
-    println!("\n{} {}...", style("🧹").cyan(), "npm Cache");
-    match cleanup_npm(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
-    }
+    let profile = super::profiles::find_profile("caches")?;
+    super::profiles::run_profile("caches", &profile, dry_run, yes).await?;
     
-    println!("\n{} {}...", style("🧹").cyan(), "Composer Cache");
-    match cleanup_composer(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
-    }
-    
-    println!("\n{} {}...", style("🧹").cyan(), "node-gyp Cache");
-    match cleanup_node_gyp(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
-    }
-    
-    println!("\n{} {}...", style("🧹").cyan(), "Playwright Cache");
-    match cleanup_playwright(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
-    }
-    
-    println!("\n{} {}...", style("🧹").cyan(), "Browser Caches");
-    match cleanup_browser_caches(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
-    }
+    Ok(())
+}
+
+/// Cleans one of the plain cache directories. With `min_age_days` only files
+/// that have not been modified for that long are removed.
+pub async fn cleanup_cache_target(target: &str, min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let (_, relative_path, name) = DIRECTORY_CACHES
+        .iter()
+        .find(|(key, _, _)| *key == target)
+        .ok_or_else(|| anyhow::anyhow!("Unknown cache target '{}'", target))?;
     
-    println!("\n{} {}...", style("🧹").cyan(), "System Temp Files");
-    match cleanup_temp_files(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
-    }
+    let home = env::var("HOME")?;
+    let cache_path = format!("{}/{}", home, relative_path);
     
-    if total_freed > 0 {
-        println!("\n{} Total freed: {}", 
-            style("🎉").green(),
-            crate::utils::format_size(total_freed)
-        );
+    match min_age_days {
+        Some(days) => cleanup_directory_older_than(&cache_path, name, days, dry_run, yes).await,
+        None => cleanup_directory(&cache_path, name, dry_run, yes).await,
     }
-    
-    Ok(())
 }
 
 pub async fn cleanup_homebrew(dry_run: bool, yes: bool) -> Result<u64> {
-    let home = env::var("HOME")?;
-    let cache_path = format!("{}/Library/Caches/Homebrew", home);
-    
-    cleanup_directory(&cache_path, "Homebrew cache", dry_run, yes).await
+    cleanup_cache_target("homebrew", None, dry_run, yes).await
 }
 
 pub async fn cleanup_pip(dry_run: bool, yes: bool) -> Result<u64> {
-    let home = env::var("HOME")?;
-    let cache_path = format!("{}/Library/Caches/pip", home);
-    
-    cleanup_directory(&cache_path, "pip cache", dry_run, yes).await
+    cleanup_cache_target("pip", None, dry_run, yes).await
 }
 
 pub async fn cleanup_npm(dry_run: bool, yes: bool) -> Result<u64> {
@@ -191,132 +90,95 @@ pub async fn cleanup_npm(dry_run: bool, yes: bool) -> Result<u64> {
 }
 
 pub async fn cleanup_composer(dry_run: bool, yes: bool) -> Result<u64> {
-    let home = env::var("HOME")?;
-    let cache_path = format!("{}/Library/Caches/composer", home);
-    
-    cleanup_directory(&cache_path, "Composer cache", dry_run, yes).await
+    cleanup_cache_target("composer", None, dry_run, yes).await
 }
 
 pub async fn cleanup_node_gyp(dry_run: bool, yes: bool) -> Result<u64> {
-    let home = env::var("HOME")?;
-    let cache_path = format!("{}/Library/Caches/node-gyp", home);
-    
-    cleanup_directory(&cache_path, "node-gyp cache", dry_run, yes).await
+    cleanup_cache_target("node-gyp", None, dry_run, yes).await
 }
 
 pub async fn cleanup_playwright(dry_run: bool, yes: bool) -> Result<u64> {
-    let home = env::var("HOME")?;
-    let cache_path = format!("{}/Library/Caches/ms-playwright", home);
-    
-    cleanup_directory(&cache_path, "Playwright cache", dry_run, yes).await
+    cleanup_cache_target("playwright", None, dry_run, yes).await
 }
 
-pub async fn cleanup_browser_caches(dry_run: bool, yes: bool) -> Result<u64> {
-    let home = env::var("HOME")?;
+async fn cleanup_directory(path: &str, name: &str, dry_run: bool, yes: bool) -> Result<u64> {
+    let path_obj = Path::new(path);
     
-    let browser_caches = vec![
-        format!("{}/Library/Caches/Google/Chrome", home),
-        format!("{}/Library/Caches/com.apple.Safari", home),
-        format!("{}/Library/Caches/Firefox", home),
-    ];
+    if !path_obj.exists() {
+        return Ok(0);
+    }
     
-    let mut total_freed = 0u64;
+    let size = calculate_directory_size(path_obj)?;
     
-    for cache_path in browser_caches {
-        if Path::new(&cache_path).exists() {
-            let size = calculate_directory_size(Path::new(&cache_path))?;
-            if size > 0 {
-                if dry_run {
-                    println!("    Would clean {}: {}", 
-                        Path::new(&cache_path).file_name().unwrap_or_default().to_string_lossy(),
-                        crate::utils::format_size(size)
-                    );
-                    total_freed += size;
-                } else {
-                    let browser_name = Path::new(&cache_path).file_name()
-                        .unwrap_or_default().to_string_lossy();
-                    
-                    if yes || Confirm::new()
-                        .with_prompt(&format!("Clear {} cache? (Browser should be closed)", browser_name))
-                        .default(false)
-                        .interact()? 
-                    {
-                        if let Err(e) = fs::remove_dir_all(&cache_path) {
-                            println!("    Warning: Could not clean {}: {} (browser may be running)", 
-                                browser_name, e);
-                        } else {
-                            total_freed += size;
-                        }
-                    }
-                }
-            }
-        }
+    if size == 0 {
+        return Ok(0);
     }
     
-    Ok(total_freed)
-}
-
-pub async fn cleanup_temp_files(_dry_run: bool, _yes: bool) -> Result<u64> {
-    // For safety, we'll only clean very specific temp locations
-    let temp_dirs = vec![
-        "/tmp",
-        "/var/tmp",
-    ];
-    
-    let total_freed = 0u64;
+    if dry_run {
+        println!("  Would clean {}: {}", name, crate::utils::format_size(size));
+        return Ok(size);
+    }
     
-    for temp_dir in temp_dirs {
-        if Path::new(temp_dir).exists() {
-            // Only clean files older than 7 days for safety
-            let output = Command::new("find")
-                .args(&[temp_dir, "-type", "f", "-mtime", "+7", "-exec", "rm", "{}", "+"])
-                .output();
-                
-            match output {
-                Ok(_) => {
-                    println!("    Cleaned old temp files from {}", temp_dir);
-                    // Note: we can't easily calculate the size freed here
-                }
-                Err(e) => {
-                    println!("    Could not clean {}: {}", temp_dir, e);
-                }
-            }
+    if yes || Confirm::new()
+        .with_prompt(&format!("Clean {}? ({})", name, crate::utils::format_size(size)))
+        .default(true)
+        .interact()? 
+    {
+        fs::remove_dir_all(path)?;
+        // Recreate the directory if it's a system cache
+        if name.contains("Cache") {
+            fs::create_dir_all(path).ok();
         }
+        Ok(size)
+    } else {
+        Ok(0)
     }
-    
-    Ok(total_freed)
 }
 
-async fn cleanup_directory(path: &str, name: &str, dry_run: bool, yes: bool) -> Result<u64> {
+async fn cleanup_directory_older_than(path: &str, name: &str, days: u64, dry_run: bool, yes: bool) -> Result<u64> {
     let path_obj = Path::new(path);
     
     if !path_obj.exists() {
         return Ok(0);
     }
     
-    let size = calculate_directory_size(path_obj)?;
+    let cutoff = SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60);
+    let mut old_files = Vec::new();
+    let mut size = 0u64;
+    
+    for entry in WalkDir::new(path_obj).into_iter().filter_map(|e| e.ok()) {
+        if let Ok(metadata) = entry.metadata() {
+            if metadata.is_file() && metadata.modified().is_ok_and(|modified| modified < cutoff) {
+                size += metadata.len();
+                old_files.push(entry.into_path());
+            }
+        }
+    }
     
     if size == 0 {
         return Ok(0);
     }
     
     if dry_run {
-        println!("  Would clean {}: {}", name, crate::utils::format_size(size));
+        println!("  Would clean {} (older than {} days): {}", name, days, crate::utils::format_size(size));
         return Ok(size);
     }
     
     if yes || Confirm::new()
-        .with_prompt(&format!("Clean {}? ({})", name, crate::utils::format_size(size)))
+        .with_prompt(format!("Clean {} files older than {} days? ({})", name, days, crate::utils::format_size(size)))
         .default(true)
         .interact()? 
     {
-        fs::remove_dir_all(path)?;
-        // Recreate the directory if it's a system cache
-        if name.contains("Cache") {
-            fs::create_dir_all(path).ok();
+        let mut freed = 0u64;
+        for file in old_files {
+            if let Ok(metadata) = fs::metadata(&file) {
+                if fs::remove_file(&file).is_ok() {
+                    freed += metadata.len();
+                }
+            }
         }
-        Ok(size)
+        Ok(freed)
     } else {
         Ok(0)
     }
-}
\ No newline at end of file
+}
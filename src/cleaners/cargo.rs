@@ -0,0 +1,265 @@
+use anyhow::Result;
+use console::style;
+use std::collections::HashSet;
+use std::env;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use super::Removal;
+use crate::utils::{calculate_directory_size, days_ago, format_size, last_used, last_used_in, read_dirs};
+
+/// Registry entries and git checkouts unused for this long are pruned by default.
+const DEFAULT_MAX_UNUSED_DAYS: u64 = 90;
+
+/// Prunes `~/.cargo` by last use instead of wiping it, and removes build artifacts
+/// in project `target/` directories that were produced by toolchains that are no
+/// longer installed. Incremental caches are left alone.
+pub async fn cleanup_cargo(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let cargo_home = cargo_home();
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_UNUSED_DAYS);
+    let cutoff = days_ago(days);
+    let mut total_freed = 0u64;
+
+    if cargo_home.exists() {
+        let registry = find_stale_registry_entries(&cargo_home, cutoff);
+        println!("  {} Registry crates unused for {} days: {}",
+            style("📦").cyan(), days, format_size(registry.iter().map(|r| r.size).sum()));
+        total_freed += super::remove_with_confirmation("unused registry crates", &registry, dry_run, yes)?;
+
+        let checkouts = find_stale_git_checkouts(&cargo_home, cutoff);
+        println!("  {} Git checkouts unused for {} days: {}",
+            style("🌿").cyan(), days, format_size(checkouts.iter().map(|r| r.size).sum()));
+        total_freed += super::remove_with_confirmation("stale git checkouts", &checkouts, dry_run, yes)?;
+    } else {
+        println!("  {} {} not found", style("ℹ").dim(), cargo_home.display());
+    }
+
+    let installed = installed_rustc_versions();
+    if installed.is_empty() {
+        println!("  {} No rustc found, skipping target/ directories", style("ℹ").blue());
+        return Ok(total_freed);
+    }
+
+    let roots = crate::config::load()?.project_roots();
+    let mut artifacts = Vec::new();
+    for target_dir in find_target_dirs(&roots) {
+        artifacts.extend(find_foreign_artifacts(&target_dir, &installed));
+    }
+    println!("  {} Build artifacts from uninstalled toolchains: {}",
+        style("🦀").cyan(), format_size(artifacts.iter().map(|r| r.size).sum()));
+    total_freed += super::remove_with_confirmation("artifacts from uninstalled toolchains", &artifacts, dry_run, yes)?;
+
+    Ok(total_freed)
+}
+
+pub fn cargo_home() -> PathBuf {
+    match env::var("CARGO_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(shellexpand::tilde("~/.cargo").as_ref()),
+    }
+}
+
+/// `.crate` archives in `registry/cache` and unpacked sources in `registry/src`
+/// that have not been used since `cutoff`.
+fn find_stale_registry_entries(cargo_home: &Path, cutoff: SystemTime) -> Vec<Removal> {
+    let mut removals = Vec::new();
+
+    for (kind, dir) in [("cache", cargo_home.join("registry/cache")), ("src", cargo_home.join("registry/src"))] {
+        // One subdirectory per registry index, e.g. index.crates.io-6f17d22bba15001f
+        for index in read_dirs(&dir) {
+            for entry in fs::read_dir(&index).into_iter().flatten().flatten() {
+                let path = entry.path();
+                let Ok(metadata) = entry.metadata() else { continue };
+
+                let (used, size) = if metadata.is_dir() {
+                    (last_used_in(&path), calculate_directory_size(&path).unwrap_or(0))
+                } else {
+                    (last_used(&metadata), metadata.len())
+                };
+
+                if used < cutoff {
+                    removals.push(Removal {
+                        description: format!("registry/{} {}", kind, entry.file_name().to_string_lossy()),
+                        path,
+                        size,
+                    });
+                }
+            }
+        }
+    }
+
+    removals
+}
+
+/// Checked-out revisions in `git/checkouts/<repo>/<rev>` unused since `cutoff`.
+/// The bare clones in `git/db` are kept so a rebuild does not need the network.
+fn find_stale_git_checkouts(cargo_home: &Path, cutoff: SystemTime) -> Vec<Removal> {
+    let mut removals = Vec::new();
+
+    for repo in read_dirs(&cargo_home.join("git/checkouts")) {
+        for revision in read_dirs(&repo) {
+            if last_used_in(&revision) < cutoff {
+                removals.push(Removal {
+                    description: format!("git/checkouts/{}/{}",
+                        repo.file_name().unwrap_or_default().to_string_lossy(),
+                        revision.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    size: calculate_directory_size(&revision).unwrap_or(0),
+                    path: revision,
+                });
+            }
+        }
+    }
+
+    removals
+}
+
+/// Version strings such as `rustc 1.75.0 (82e1608df 2023-12-21)` for every
+/// installed toolchain, matching what rustc embeds in its metadata files.
+fn installed_rustc_versions() -> HashSet<String> {
+    let mut versions = HashSet::new();
+
+    let toolchains = Command::new("rustup")
+        .args(["toolchain", "list"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+        .unwrap_or_default();
+
+    for line in toolchains.lines() {
+        if let Some(toolchain) = line.split_whitespace().next() {
+            if let Some(version) = rustc_version(&[&format!("+{}", toolchain)]) {
+                versions.insert(version);
+            }
+        }
+    }
+
+    // Without rustup, whatever rustc is on the PATH is the only toolchain
+    if versions.is_empty() {
+        if let Some(version) = rustc_version(&[]) {
+            versions.insert(version);
+        }
+    }
+
+    versions
+}
+
+fn rustc_version(toolchain: &[&str]) -> Option<String> {
+    let output = Command::new("rustc").args(toolchain).arg("-V").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// `target/` directories of Cargo projects under the project roots.
+fn find_target_dirs(roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut target_dirs = Vec::new();
+
+    for root in roots {
+        for entry in crate::utils::walk_project_root(root) {
+            if entry.file_name() == "Cargo.toml" {
+                if let Some(project) = entry.path().parent() {
+                    let target = project.join("target");
+                    // CACHEDIR.TAG is written by Cargo into every target directory
+                    if target.join("CACHEDIR.TAG").exists() && !target_dirs.contains(&target) {
+                        target_dirs.push(target);
+                    }
+                }
+            }
+        }
+    }
+
+    target_dirs
+}
+
+/// Artifacts in `deps/` whose metadata was written by a rustc that is not in
+/// `installed`, along with their fingerprints. Units whose version cannot be
+/// read (proc macros, binaries) are kept.
+fn find_foreign_artifacts(target_dir: &Path, installed: &HashSet<String>) -> Vec<Removal> {
+    let mut removals = Vec::new();
+
+    // Profiles live in target/<profile> or, when cross compiling, target/<triple>/<profile>
+    let mut profile_dirs = Vec::new();
+    for dir in read_dirs(target_dir) {
+        if dir.join("deps").is_dir() {
+            profile_dirs.push(dir);
+        } else {
+            profile_dirs.extend(read_dirs(&dir).into_iter().filter(|sub| sub.join("deps").is_dir()));
+        }
+    }
+
+    for profile_dir in profile_dirs {
+        let deps = profile_dir.join("deps");
+        let mut foreign_units = Vec::new();
+
+        for entry in fs::read_dir(&deps).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "rmeta") {
+                continue;
+            }
+
+            if let Some(version) = read_rmeta_version(&path) {
+                if !installed.contains(&version) {
+                    // libfoo-0123abcd.rmeta -> foo-0123abcd
+                    let stem = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+                    foreign_units.push((stem.strip_prefix("lib").unwrap_or(&stem).to_string(), version));
+                }
+            }
+        }
+
+        for (unit, version) in foreign_units {
+            let mut paths: Vec<PathBuf> = fs::read_dir(&deps)
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    let name = path.file_name().unwrap_or_default().to_string_lossy();
+                    name.starts_with(&format!("lib{}.", unit)) || name.starts_with(&format!("{}.", unit))
+                })
+                .collect();
+
+            let fingerprint = profile_dir.join(".fingerprint").join(&unit);
+            if fingerprint.exists() {
+                paths.push(fingerprint);
+            }
+
+            for path in paths {
+                let size = if path.is_dir() {
+                    calculate_directory_size(&path).unwrap_or(0)
+                } else {
+                    fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+                };
+                removals.push(Removal {
+                    description: format!("{} ({})", path.display(), version),
+                    path,
+                    size,
+                });
+            }
+        }
+    }
+
+    removals
+}
+
+/// Reads the `rustc x.y.z (hash date)` string rustc stores near the start of
+/// every `.rmeta` file.
+fn read_rmeta_version(path: &Path) -> Option<String> {
+    let mut header = [0u8; 256];
+    let read = File::open(path).ok()?.read(&mut header).ok()?;
+    let header = &header[..read];
+
+    if !header.starts_with(b"rust") {
+        return None;
+    }
+
+    let start = header.windows(6).position(|window| window == b"rustc ")?;
+    let end = start + header[start..].iter().position(|b| *b == b')')? + 1;
+
+    String::from_utf8(header[start..end].to_vec()).ok()
+}
@@ -0,0 +1,362 @@
+use anyhow::Result;
+use console::style;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::Removal;
+use crate::utils::{calculate_directory_size, days_ago, effective_uid, format_date, format_size};
+
+/// Crashes newer than this are kept by default, since they may still be debugged.
+const DEFAULT_MIN_AGE_DAYS: u64 = 7;
+
+const SYSTEMD_COREDUMP_DIR: &str = "/var/lib/systemd/coredump";
+const APPORT_DIR: &str = "/var/crash";
+const ABRT_DIR: &str = "/var/spool/abrt";
+
+/// Report extensions in macOS `DiagnosticReports` directories.
+const DIAGNOSTIC_REPORT_EXTENSIONS: &[&str] = &["ips", "crash", "hang", "spin", "diag"];
+
+/// A core dump or crash report, and the program that crashed.
+pub struct CrashDump {
+    pub path: PathBuf,
+    pub size: u64,
+    pub binary: String,
+    pub date: SystemTime,
+    pub kind: &'static str,
+    /// Lives in a system directory or belongs to another user, so removing it
+    /// needs root and `--system`
+    pub system: bool,
+}
+
+/// Removes core dumps and crash reports older than `min_age_days`: systemd-coredump,
+/// apport and ABRT on Linux, `DiagnosticReports` and `/cores` on macOS, and stray
+/// `core` files in project directories.
+pub async fn cleanup_crash_dumps(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let days = min_age_days.unwrap_or(DEFAULT_MIN_AGE_DAYS);
+    let cutoff = days_ago(days);
+
+    let dumps = find_crash_dumps(&crate::config::load()?.project_roots());
+    if dumps.is_empty() {
+        println!("  {} No core dumps or crash reports found", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    let total: u64 = dumps.iter().map(|dump| dump.size).sum();
+    println!("  {} {} core dumps and crash reports: {}", style("💥").cyan(), dumps.len(), format_size(total));
+
+    let (system, user): (Vec<CrashDump>, Vec<CrashDump>) = dumps
+        .into_iter()
+        .filter(|dump| dump.date < cutoff)
+        .partition(|dump| dump.system);
+
+    let mut total_freed = super::remove_with_confirmation(
+        &format!("crash dumps older than {} days", days),
+        &removals(user),
+        dry_run,
+        yes,
+    )?;
+
+    if !system.is_empty() && super::system::system_cleaners_allowed() {
+        total_freed += super::remove_with_confirmation(
+            &format!("system crash dumps older than {} days", days),
+            &removals(system),
+            dry_run,
+            yes,
+        )?;
+    }
+
+    Ok(total_freed)
+}
+
+/// Every core dump and crash report this process can see, largest first.
+pub fn find_crash_dumps(project_roots: &[PathBuf]) -> Vec<CrashDump> {
+    let mut dumps = Vec::new();
+
+    for path in files_in(Path::new(SYSTEMD_COREDUMP_DIR)) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let binary = coredump_program(&name).unwrap_or_default();
+        dumps.extend(crash_dump(path, binary, "systemd-coredump", true));
+    }
+
+    // apport writes <executable path with _ for />.<uid>.crash
+    for path in files_in(Path::new(APPORT_DIR)) {
+        if path.extension().is_none_or(|ext| ext != "crash") {
+            continue;
+        }
+        let binary = report_field(&path, "ExecutablePath:").unwrap_or_default();
+        dumps.extend(crash_dump(path, binary, "apport", false));
+    }
+
+    // ABRT keeps one directory per problem, with the binary in `executable`
+    for dir in fs::read_dir(ABRT_DIR).into_iter().flatten().flatten().map(|entry| entry.path()) {
+        if !dir.is_dir() {
+            continue;
+        }
+        let binary = fs::read_to_string(dir.join("executable")).unwrap_or_default().trim().to_string();
+        dumps.extend(crash_dump(dir, binary, "ABRT", true));
+    }
+
+    if cfg!(target_os = "macos") {
+        for reports in [
+            PathBuf::from(shellexpand::tilde("~/Library/Logs/DiagnosticReports").as_ref()),
+            PathBuf::from("/Library/Logs/DiagnosticReports"),
+        ] {
+            for path in files_in(&reports) {
+                let is_report = path.extension().is_some_and(|ext| DIAGNOSTIC_REPORT_EXTENSIONS.iter().any(|e| ext == *e));
+                if is_report {
+                    let binary = diagnostic_report_process(&path);
+                    dumps.extend(crash_dump(path, binary, "DiagnosticReports", false));
+                }
+            }
+        }
+        for path in files_in(Path::new("/cores")) {
+            dumps.extend(crash_dump(path, String::new(), "core", false));
+        }
+    }
+
+    for root in project_roots {
+        for entry in crate::utils::walk_project_root(root) {
+            let name = entry.file_name().to_string_lossy();
+            let is_core_name = name == "core"
+                || name.strip_prefix("core.").is_some_and(|pid| !pid.is_empty() && pid.bytes().all(|b| b.is_ascii_digit()));
+            if !is_core_name || !entry.file_type().is_file() {
+                continue;
+            }
+            // Plenty of source files are called `core`; only ELF core files count
+            if let Some(binary) = elf_core_program(entry.path()) {
+                dumps.extend(crash_dump(entry.into_path(), binary, "core", false));
+            }
+        }
+    }
+
+    dumps.sort_by_key(|dump| std::cmp::Reverse(dump.size));
+    dumps
+}
+
+fn crash_dump(path: PathBuf, binary: String, kind: &'static str, system: bool) -> Option<CrashDump> {
+    let metadata = fs::metadata(&path).ok()?;
+    let size = if metadata.is_dir() { calculate_directory_size(&path).unwrap_or(0) } else { metadata.len() };
+    let owned = metadata.uid() == effective_uid();
+
+    Some(CrashDump {
+        binary: if binary.is_empty() { "unknown".to_string() } else { binary },
+        date: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        system: system || !owned,
+        path,
+        size,
+        kind,
+    })
+}
+
+fn removals(dumps: Vec<CrashDump>) -> Vec<Removal> {
+    dumps
+        .into_iter()
+        .map(|dump| Removal {
+            description: format!("{} {} ({}, {})", dump.kind, dump.binary, format_date(dump.date), dump.path.display()),
+            path: dump.path,
+            size: dump.size,
+        })
+        .collect()
+}
+
+/// The program in a systemd-coredump file name,
+/// `core.<comm>.<uid>.<boot id>.<pid>.<usec>[.zst|.xz|.lz4]`. The command name
+/// may itself contain dots, so the fields are taken from the end.
+fn coredump_program(name: &str) -> Option<String> {
+    let name = [".zst", ".xz", ".lz4"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(name);
+    let comm = name.strip_prefix("core.")?.rsplitn(5, '.').nth(4)?;
+    Some(comm.replace("\\x2f", "/"))
+}
+
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .collect()
+}
+
+/// The value of a `Key: value` line near the top of a text crash report.
+fn report_field(path: &Path, key: &str) -> Option<String> {
+    let mut head = String::new();
+    // Reports embed the core dump further down; the header is enough
+    File::open(path).ok()?.take(16 * 1024).read_to_string(&mut head).ok();
+    head.lines().find_map(|line| line.strip_prefix(key)).map(|value| value.trim().to_string())
+}
+
+/// The crashed process of a macOS report: `app_name` in the JSON header of
+/// `.ips` files, the `Process:` line of older formats.
+fn diagnostic_report_process(path: &Path) -> String {
+    if path.extension().is_some_and(|ext| ext == "ips") {
+        let header = report_field(path, "{").map(|rest| format!("{{{}", rest));
+        let name = header
+            .and_then(|header| serde_json::from_str::<serde_json::Value>(&header).ok())
+            .and_then(|header| header["app_name"].as_str().or_else(|| header["name"].as_str()).map(String::from));
+        if let Some(name) = name {
+            return name;
+        }
+    }
+    report_field(path, "Process:")
+        .map(|process| process.split(" [").next().unwrap_or_default().trim().to_string())
+        .unwrap_or_default()
+}
+
+/// The program name recorded in the `NT_PRPSINFO` note of an ELF core file, or
+/// `None` if `path` is not one.
+fn elf_core_program(path: &Path) -> Option<String> {
+    let mut file = File::open(path).ok()?;
+    let mut header = [0u8; 64];
+    file.read_exact(&mut header).ok()?;
+    if &header[..4] != b"\x7fELF" {
+        return None;
+    }
+
+    let is_64 = header[4] == 2;
+    let little_endian = header[5] == 1;
+    let u16_at = |bytes: &[u8], at: usize| {
+        let raw = [bytes[at], bytes[at + 1]];
+        if little_endian { u16::from_le_bytes(raw) } else { u16::from_be_bytes(raw) }
+    };
+    let u32_at = |bytes: &[u8], at: usize| {
+        let raw = bytes[at..at + 4].try_into().unwrap_or_default();
+        if little_endian { u32::from_le_bytes(raw) } else { u32::from_be_bytes(raw) }
+    };
+    let u64_at = |bytes: &[u8], at: usize| {
+        let raw = bytes[at..at + 8].try_into().unwrap_or_default();
+        if little_endian { u64::from_le_bytes(raw) } else { u64::from_be_bytes(raw) }
+    };
+
+    // e_type 4 is ET_CORE
+    if u16_at(&header, 16) != 4 {
+        return None;
+    }
+    let (phoff, phentsize, phnum) = if is_64 {
+        (u64_at(&header, 32), u16_at(&header, 54) as usize, u16_at(&header, 56) as usize)
+    } else {
+        (u32_at(&header, 28) as u64, u16_at(&header, 42) as usize, u16_at(&header, 44) as usize)
+    };
+    if phentsize < 32 || phnum > 4096 {
+        return Some(String::new());
+    }
+
+    let mut program_headers = vec![0u8; phentsize * phnum];
+    file.seek(SeekFrom::Start(phoff)).ok()?;
+    file.read_exact(&mut program_headers).ok()?;
+
+    for header in program_headers.chunks(phentsize) {
+        // p_type 4 is PT_NOTE
+        if u32_at(header, 0) != 4 {
+            continue;
+        }
+        let (offset, size) = if is_64 {
+            (u64_at(header, 8), u64_at(header, 32))
+        } else {
+            (u32_at(header, 4) as u64, u32_at(header, 16) as u64)
+        };
+        let mut notes = vec![0u8; size.min(1024 * 1024) as usize];
+        if file.seek(SeekFrom::Start(offset)).is_err() || file.read_exact(&mut notes).is_err() {
+            continue;
+        }
+
+        let mut at = 0;
+        while at + 12 <= notes.len() {
+            let name_size = u32_at(&notes, at) as usize;
+            let desc_size = u32_at(&notes, at + 4) as usize;
+            let note_type = u32_at(&notes, at + 8);
+            let desc = at + 12 + name_size.div_ceil(4) * 4;
+
+            // NT_PRPSINFO; pr_fname sits after the flags, ids and pids
+            if note_type == 3 {
+                let fname = if is_64 { 40 } else { 28 };
+                let name = notes.get(desc + fname..desc + fname + 16)?;
+                let end = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+                return Some(String::from_utf8_lossy(&name[..end]).into_owned());
+            }
+            at = desc + desc_size.div_ceil(4) * 4;
+        }
+    }
+
+    Some(String::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("spacecleaner-{}-{}", name, std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    /// A 64-bit little-endian ELF core with one PT_NOTE holding an NT_PRPSINFO note.
+    fn elf_core(program: &str) -> Vec<u8> {
+        let mut core = vec![0u8; 64];
+        core[..4].copy_from_slice(b"\x7fELF");
+        core[4] = 2;
+        core[5] = 1;
+        core[16..18].copy_from_slice(&4u16.to_le_bytes());
+        core[32..40].copy_from_slice(&64u64.to_le_bytes());
+        core[54..56].copy_from_slice(&56u16.to_le_bytes());
+        core[56..58].copy_from_slice(&1u16.to_le_bytes());
+
+        let mut note = Vec::new();
+        note.extend_from_slice(&5u32.to_le_bytes());
+        note.extend_from_slice(&136u32.to_le_bytes());
+        note.extend_from_slice(&3u32.to_le_bytes());
+        note.extend_from_slice(b"CORE\0\0\0\0");
+        let mut prpsinfo = vec![0u8; 136];
+        prpsinfo[40..40 + program.len()].copy_from_slice(program.as_bytes());
+        note.extend_from_slice(&prpsinfo);
+
+        let mut program_header = vec![0u8; 56];
+        program_header[..4].copy_from_slice(&4u32.to_le_bytes());
+        program_header[8..16].copy_from_slice(&120u64.to_le_bytes());
+        program_header[32..40].copy_from_slice(&(note.len() as u64).to_le_bytes());
+
+        core.extend_from_slice(&program_header);
+        core.extend_from_slice(&note);
+        core
+    }
+
+    #[test]
+    fn elf_core_program_reads_prpsinfo() {
+        let core = temp_file("core", &elf_core("myserver"));
+        let source = temp_file("core-source", b"pub mod core;\n");
+        let mut executable = elf_core("myserver");
+        executable[16..18].copy_from_slice(&2u16.to_le_bytes());
+        let executable = temp_file("core-exec", &executable);
+
+        assert_eq!(elf_core_program(&core).as_deref(), Some("myserver"));
+        assert_eq!(elf_core_program(&source), None);
+        assert_eq!(elf_core_program(&executable), None);
+        for path in [core, source, executable] {
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn coredump_program_parses_from_the_end() {
+        assert_eq!(
+            coredump_program("core.python3.11.1000.0123456789abcdef0123456789abcdef.4242.1700000000000000.zst").as_deref(),
+            Some("python3.11")
+        );
+        assert_eq!(
+            coredump_program("core.bash.1000.0123456789abcdef0123456789abcdef.17.1700000000000000").as_deref(),
+            Some("bash")
+        );
+        assert_eq!(
+            coredump_program("core.\\x2fusr\\x2fbin\\x2fapp.0.bootid.1.2.lz4").as_deref(),
+            Some("/usr/bin/app")
+        );
+        assert_eq!(coredump_program("core.truncated.1000"), None);
+    }
+}
@@ -0,0 +1,249 @@
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDateTime, TimeDelta, Utc};
+use console::style;
+use dialoguer::Confirm;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+use super::Removal;
+use crate::utils::{cache_home, calculate_directory_size, data_home, file_uri_to_path, format_size, percent_decode};
+
+/// Trash items and recent-file entries older than this are removed by default.
+const DEFAULT_MAX_AGE_DAYS: u64 = 30;
+
+/// Thumbnail sizes defined by the Thumbnail Managing Standard, plus failures.
+const THUMBNAIL_DIRS: &[&str] = &["normal", "large", "x-large", "xx-large", "fail"];
+
+/// Runs the Trash, thumbnail and recent-files cleaners in turn.
+pub async fn cleanup_desktop(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    println!("  {} Trash", style("🗑️").cyan());
+    total_freed += cleanup_trash(min_age_days, dry_run, yes).await?;
+    println!("  {} Thumbnails", style("🖼️").cyan());
+    total_freed += cleanup_thumbnails(dry_run, yes).await?;
+    println!("  {} Recent files", style("🕘").cyan());
+    total_freed += cleanup_recent_files(min_age_days, dry_run, yes).await?;
+
+    Ok(total_freed)
+}
+
+/// Empties Trash items deleted more than `min_age_days` ago, going by the
+/// `DeletionDate` in each item's `info/<name>.trashinfo`.
+pub async fn cleanup_trash(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let trash = data_home().join("Trash");
+    if !trash.join("info").is_dir() {
+        println!("    {} {} not found", style("ℹ").dim(), trash.display());
+        return Ok(0);
+    }
+
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_AGE_DAYS);
+    let cutoff = Local::now().naive_local() - TimeDelta::days(days as i64);
+    let mut removals = Vec::new();
+    let mut total_size = 0u64;
+
+    for entry in fs::read_dir(trash.join("info")).into_iter().flatten().flatten() {
+        let info = entry.path();
+        if info.extension().is_none_or(|ext| ext != "trashinfo") {
+            continue;
+        }
+        let item = trash.join("files").join(info.file_stem().unwrap_or_default());
+        let size = if item.is_dir() {
+            calculate_directory_size(&item).unwrap_or(0)
+        } else {
+            fs::symlink_metadata(&item).map(|m| m.len()).unwrap_or(0)
+        };
+        total_size += size;
+
+        let Some((original, deleted)) = read_trash_info(&info) else { continue };
+        if deleted >= cutoff {
+            continue;
+        }
+
+        let description = format!("{} (deleted {})", original, deleted.format("%Y-%m-%d"));
+        if fs::symlink_metadata(&item).is_ok() {
+            removals.push(Removal { path: item, size, description });
+        }
+        // The info file goes last so an interrupted run leaves no orphaned item
+        removals.push(Removal {
+            description: format!("{}.trashinfo", info.file_stem().unwrap_or_default().to_string_lossy()),
+            size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+            path: info,
+        });
+    }
+
+    println!("    Trash holds {}; items deleted over {} days ago: {}",
+        format_size(total_size), days, format_size(removals.iter().map(|r| r.size).sum()));
+    super::remove_with_confirmation("old Trash items", &removals, dry_run, yes)
+}
+
+/// Removes thumbnails whose source file no longer exists, as recorded in the
+/// `Thumb::URI` text chunk of each PNG. Thumbnails of remote files are kept.
+pub async fn cleanup_thumbnails(dry_run: bool, yes: bool) -> Result<u64> {
+    let thumbnails = cache_home().join("thumbnails");
+    if !thumbnails.is_dir() {
+        println!("    {} {} not found", style("ℹ").dim(), thumbnails.display());
+        return Ok(0);
+    }
+
+    let mut removals = Vec::new();
+    let mut total_size = 0u64;
+
+    for size_dir in THUMBNAIL_DIRS {
+        // fail/ has one subdirectory per thumbnailer
+        let dir = thumbnails.join(size_dir);
+        let dirs = if *size_dir == "fail" {
+            fs::read_dir(&dir).into_iter().flatten().flatten().map(|entry| entry.path()).collect()
+        } else {
+            vec![dir]
+        };
+
+        for entry in dirs.iter().flat_map(|dir| fs::read_dir(dir).into_iter().flatten().flatten()) {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else { continue };
+            if !metadata.is_file() {
+                continue;
+            }
+            total_size += metadata.len();
+
+            let source = read_thumbnail_uri(&path).and_then(|uri| file_uri_to_path(&uri));
+            if let Some(source) = source.filter(|source| !source.exists()) {
+                removals.push(Removal {
+                    description: format!("{} ({})", source.display(), size_dir),
+                    size: metadata.len(),
+                    path,
+                });
+            }
+        }
+    }
+
+    println!("    Thumbnails take {}; of deleted files: {}",
+        format_size(total_size), format_size(removals.iter().map(|r| r.size).sum()));
+    super::remove_with_confirmation("orphaned thumbnails", &removals, dry_run, yes)
+}
+
+/// Drops entries from `recently-used.xbel` whose file is gone or that were last
+/// visited more than `min_age_days` ago.
+pub async fn cleanup_recent_files(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let path = data_home().join("recently-used.xbel");
+    let Ok(contents) = fs::read_to_string(&path) else {
+        println!("    {} {} not found", style("ℹ").dim(), path.display());
+        return Ok(0);
+    };
+
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_AGE_DAYS);
+    // GLib writes the timestamps in UTC, so compare against UTC rather than local time
+    let cutoff = Utc::now() - TimeDelta::days(days as i64);
+
+    // The file is a flat list of <bookmark>...</bookmark> elements written by
+    // GLib, so entries can be cut out without a full XML parser
+    let (Some(first), Some(last)) = (contents.find("<bookmark "), contents.rfind("</bookmark>")) else {
+        println!("    {} No recent files recorded", style("ℹ").blue());
+        return Ok(0);
+    };
+    let end = last + "</bookmark>".len();
+
+    let mut kept = String::with_capacity(contents.len());
+    kept.push_str(&contents[..first]);
+    let mut dropped = 0usize;
+    let mut total = 0usize;
+
+    for bookmark in contents[first..end].split_inclusive("</bookmark>") {
+        total += 1;
+        let href = attribute(bookmark, "href");
+        let visited = attribute(bookmark, "visited")
+            .or_else(|| attribute(bookmark, "modified"))
+            .and_then(|date| DateTime::parse_from_rfc3339(date).ok())
+            .map(|date| date.with_timezone(&Utc));
+
+        // XML attributes escape & as &amp;
+        let missing = href
+            .and_then(|href| file_uri_to_path(&href.replace("&amp;", "&")))
+            .is_some_and(|file| !file.exists());
+        let stale = visited.is_some_and(|visited| visited < cutoff);
+        if missing || stale {
+            dropped += 1;
+        } else {
+            kept.push_str(bookmark);
+        }
+    }
+    kept.push_str(&contents[end..]);
+
+    let freed = contents.len().saturating_sub(kept.len()) as u64;
+    println!("    {} of {} entries point to deleted files or are older than {} days",
+        dropped, total, days);
+    if dropped == 0 {
+        return Ok(0);
+    }
+
+    if dry_run {
+        println!("  Would trim recently-used.xbel: {}", format_size(freed));
+        return Ok(freed);
+    }
+
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Remove {} entries from recently-used.xbel?", dropped))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    // Write next to the original and rename so a crash never leaves it truncated
+    let temp = path.with_extension("xbel.tmp");
+    fs::write(&temp, kept)?;
+    fs::rename(&temp, &path)?;
+
+    Ok(freed)
+}
+
+/// The original path and deletion time recorded in a `.trashinfo` file.
+fn read_trash_info(path: &Path) -> Option<(String, NaiveDateTime)> {
+    let contents = fs::read_to_string(path).ok()?;
+    let value = |key: &str| {
+        contents
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+            .map(str::trim)
+    };
+
+    let original = percent_decode(value("Path")?);
+    let deleted = NaiveDateTime::parse_from_str(value("DeletionDate")?, "%Y-%m-%dT%H:%M:%S").ok()?;
+    Some((original, deleted))
+}
+
+/// Reads the `Thumb::URI` value from a thumbnail's `tEXt` chunks.
+fn read_thumbnail_uri(path: &Path) -> Option<String> {
+    let mut png = Vec::new();
+    // Text chunks come before the image data; the header is plenty
+    File::open(path).ok()?.take(64 * 1024).read_to_end(&mut png).ok()?;
+    if !png.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return None;
+    }
+
+    let mut offset = 8;
+    while offset + 8 <= png.len() {
+        let length = u32::from_be_bytes(png[offset..offset + 4].try_into().ok()?) as usize;
+        let kind = &png[offset + 4..offset + 8];
+        let data = png.get(offset + 8..offset + 8 + length)?;
+
+        if kind == b"tEXt" {
+            if let Some(uri) = data.strip_prefix(b"Thumb::URI\0") {
+                return String::from_utf8(uri.to_vec()).ok();
+            }
+        } else if kind == b"IDAT" {
+            return None;
+        }
+        offset += 12 + length;
+    }
+
+    None
+}
+
+/// The value of `name="..."` in an XML start tag.
+fn attribute<'a>(element: &'a str, name: &str) -> Option<&'a str> {
+    let start = element.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let length = element[start..].find('"')?;
+    Some(&element[start..start + length])
+}
@@ -1,8 +1,31 @@
 use anyhow::Result;
 use console::style;
 use dialoguer::Confirm;
+use serde::Deserialize;
 use std::process::Command;
 
+/// What a profile's Docker target is allowed to prune.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DockerOptions {
+    pub images: bool,
+    pub containers: bool,
+    pub volumes: bool,
+    pub build_cache: bool,
+}
+
+impl Default for DockerOptions {
+    fn default() -> Self {
+        // Same defaults as the interactive prompts: volumes may hold data
+        DockerOptions {
+            images: true,
+            containers: true,
+            volumes: false,
+            build_cache: true,
+        }
+    }
+}
+
 pub async fn cleanup_docker(dry_run: bool, yes: bool) -> Result<()> {
     println!("{}", style("🐳 Docker Cleanup").bold().blue());
     
@@ -44,8 +67,8 @@ pub async fn cleanup_docker(dry_run: bool, yes: bool) -> Result<()> {
         .default(true)
         .interact()?;
     
-    if cleanup_all {
-        run_docker_cleanup(true).await?;
+    let freed = if cleanup_all {
+        run_docker_cleanup(true).await?
     } else {
         // Individual cleanup options
         let clean_images = Confirm::new()
@@ -68,12 +91,98 @@ pub async fn cleanup_docker(dry_run: bool, yes: bool) -> Result<()> {
             .default(true)
             .interact()?;
         
-        run_selective_docker_cleanup(clean_images, clean_containers, clean_volumes, clean_build_cache).await?;
-    }
+        run_selective_docker_cleanup(clean_images, clean_containers, clean_volumes, clean_build_cache).await?
+    };
+    
+    crate::history::record_cleanup_or_warn("docker", freed, dry_run);
     
     Ok(())
 }
 
+/// Docker cleanup used by profiles. Only prunes what `options` allows, asks before
+/// pruning unless `yes` is set, and returns the reclaimed bytes (the estimate in
+/// dry-run mode).
+pub async fn cleanup_docker_with_options(options: &DockerOptions, dry_run: bool, yes: bool) -> Result<u64> {
+    if !is_docker_available() {
+        println!("  {} Docker not found or not running", style("ℹ").blue());
+        return Ok(0);
+    }
+    
+    let reclaimable = reclaimable_space().unwrap_or_default();
+    let reclaimable_of = |kind: &str| {
+        reclaimable.iter().find(|(name, _)| name == kind).map(|(_, size)| *size).unwrap_or(0)
+    };
+    
+    let mut commands = Vec::new();
+    let mut estimate = 0u64;
+    if options.containers {
+        commands.push("docker container prune -f");
+        estimate += reclaimable_of("Containers");
+    }
+    if options.images {
+        commands.push("docker image prune -a -f");
+        estimate += reclaimable_of("Images");
+    }
+    if options.volumes {
+        commands.push("docker volume prune -f");
+        estimate += reclaimable_of("Local Volumes");
+    }
+    if options.build_cache {
+        commands.push("docker builder prune -a -f");
+        estimate += reclaimable_of("Build Cache");
+    }
+    
+    if commands.is_empty() {
+        return Ok(0);
+    }
+    
+    for command in &commands {
+        println!("    {}", style(command).dim());
+    }
+    
+    if dry_run {
+        println!("  Would reclaim about {}", crate::utils::format_size(estimate));
+        return Ok(estimate);
+    }
+    
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Run Docker prune? (about {} reclaimable)", crate::utils::format_size(estimate)))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
+    }
+    
+    run_selective_docker_cleanup(
+        options.images,
+        options.containers,
+        options.volumes,
+        options.build_cache,
+    ).await
+}
+
+/// Reclaimable bytes per `docker system df` row ("Images", "Containers",
+/// "Local Volumes" and "Build Cache").
+pub fn reclaimable_space() -> Result<Vec<(String, u64)>> {
+    let output = Command::new("docker")
+        .args(["system", "df", "--format", "{{.Type}}\t{{.Reclaimable}}"])
+        .output()?;
+    
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("docker system df failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (kind, reclaimable) = line.split_once('\t')?;
+            // "1.2GB (50%)"
+            let size = reclaimable.split_whitespace().next()?;
+            Some((kind.to_string(), crate::scanner::parse_size_string(size).unwrap_or(0)))
+        })
+        .collect())
+}
+
 #[derive(Debug)]
 struct DockerUsage {
     image_count: String,
@@ -130,7 +239,7 @@ fn parse_docker_system_df(output: &str) -> Result<DockerUsage> {
     Ok(usage)
 }
 
-async fn run_docker_cleanup(all: bool) -> Result<()> {
+async fn run_docker_cleanup(all: bool) -> Result<u64> {
     println!("\n{} Running Docker cleanup...", style("🧹").cyan());
     
     let args = if all {
@@ -144,12 +253,14 @@ async fn run_docker_cleanup(all: bool) -> Result<()> {
         .output()?;
     
     let output_str = String::from_utf8(output.stdout)?;
+    let mut freed = 0u64;
     
     if output.status.success() {
         // Parse the output to show what was cleaned
         if let Some(total_line) = output_str.lines().last() {
-            if total_line.starts_with("Total reclaimed space:") {
+            if let Some(size) = total_line.strip_prefix("Total reclaimed space:") {
                 println!("  {} {}", style("✓").green(), total_line);
+                freed = crate::scanner::parse_size_string(size).unwrap_or(0);
             }
         }
         
@@ -169,7 +280,7 @@ async fn run_docker_cleanup(all: bool) -> Result<()> {
         return Err(anyhow::anyhow!("Docker cleanup failed: {}", error_str));
     }
     
-    Ok(())
+    Ok(freed)
 }
 
 async fn run_selective_docker_cleanup(
@@ -177,8 +288,8 @@ async fn run_selective_docker_cleanup(
     clean_containers: bool,
     clean_volumes: bool,
     clean_build_cache: bool,
-) -> Result<()> {
-    let _total_freed = String::new();
+) -> Result<u64> {
+    let mut total_freed = 0u64;
     
     if clean_containers {
         println!("  {} Removing stopped containers...", style("🧹").cyan());
@@ -189,8 +300,9 @@ async fn run_selective_docker_cleanup(
         if output.status.success() {
             let output_str = String::from_utf8(output.stdout)?;
             if let Some(total_line) = output_str.lines().last() {
-                if total_line.starts_with("Total reclaimed space:") {
+                if let Some(size) = total_line.strip_prefix("Total reclaimed space:") {
                     println!("    {} {}", style("✓").green(), total_line);
+                    total_freed += crate::scanner::parse_size_string(size).unwrap_or(0);
                 }
             }
         }
@@ -205,8 +317,9 @@ async fn run_selective_docker_cleanup(
         if output.status.success() {
             let output_str = String::from_utf8(output.stdout)?;
             if let Some(total_line) = output_str.lines().last() {
-                if total_line.starts_with("Total reclaimed space:") {
+                if let Some(size) = total_line.strip_prefix("Total reclaimed space:") {
                     println!("    {} {}", style("✓").green(), total_line);
+                    total_freed += crate::scanner::parse_size_string(size).unwrap_or(0);
                 }
             }
         }
@@ -221,8 +334,9 @@ async fn run_selective_docker_cleanup(
         if output.status.success() {
             let output_str = String::from_utf8(output.stdout)?;
             if let Some(total_line) = output_str.lines().last() {
-                if total_line.starts_with("Total reclaimed space:") {
+                if let Some(size) = total_line.strip_prefix("Total reclaimed space:") {
                     println!("    {} {}", style("✓").green(), total_line);
+                    total_freed += crate::scanner::parse_size_string(size).unwrap_or(0);
                 }
             }
         }
@@ -237,14 +351,15 @@ async fn run_selective_docker_cleanup(
         if output.status.success() {
             let output_str = String::from_utf8(output.stdout)?;
             if let Some(total_line) = output_str.lines().last() {
-                if total_line.starts_with("Total reclaimed space:") {
+                if let Some(size) = total_line.strip_prefix("Total reclaimed space:") {
                     println!("    {} {}", style("✓").green(), total_line);
+                    total_freed += crate::scanner::parse_size_string(size).unwrap_or(0);
                 }
             }
         }
     }
     
-    Ok(())
+    Ok(total_freed)
 }
 
 fn is_docker_available() -> bool {
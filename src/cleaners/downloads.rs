@@ -0,0 +1,401 @@
+use anyhow::Result;
+use chrono::Local;
+use console::style;
+use dialoguer::Confirm;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::utils::{calculate_directory_size, config_home, data_home, days_ago, effective_uid, format_size};
+
+/// Downloads older than this are offered for trashing by default.
+const DEFAULT_MIN_AGE_DAYS: u64 = 30;
+
+/// Partial downloads left by browsers are offered once they are this old.
+const INCOMPLETE_MIN_AGE_DAYS: u64 = 1;
+
+/// Age buckets for the summary, as (label, upper bound in days).
+const AGE_BUCKETS: &[(&str, u64)] = &[("< 1 week", 7), ("< 1 month", 30), ("< 3 months", 90), ("older", u64::MAX)];
+
+const INSTALLER_EXTENSIONS: &[&str] = &[
+    "dmg", "pkg", "deb", "rpm", "appimage", "iso", "msi", "exe", "flatpakref", "snap", "run",
+];
+const ARCHIVE_EXTENSIONS: &[&str] = &[
+    "zip", "tar", "tgz", "tbz2", "txz", "gz", "xz", "bz2", "zst", "7z", "rar",
+];
+const MEDIA_EXTENSIONS: &[&str] = &[
+    "jpg", "jpeg", "png", "gif", "webp", "heic", "svg", "mp4", "mkv", "mov", "avi", "webm",
+    "mp3", "flac", "wav", "m4a", "ogg",
+];
+const DOCUMENT_EXTENSIONS: &[&str] = &[
+    "pdf", "doc", "docx", "xls", "xlsx", "ppt", "pptx", "odt", "ods", "odp", "txt", "md",
+    "csv", "epub", "rtf",
+];
+const INCOMPLETE_EXTENSIONS: &[&str] = &["crdownload", "part", "partial", "download", "opdownload"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Installer,
+    Archive,
+    /// An archive with a folder of the same name next to it
+    ExtractedArchive,
+    Incomplete,
+    Media,
+    Document,
+    Folder,
+    Other,
+}
+
+impl Category {
+    pub const ALL: &'static [Category] = &[
+        Category::Installer,
+        Category::Archive,
+        Category::ExtractedArchive,
+        Category::Incomplete,
+        Category::Media,
+        Category::Document,
+        Category::Folder,
+        Category::Other,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Category::Installer => "Installers",
+            Category::Archive => "Archives",
+            Category::ExtractedArchive => "Extracted archives",
+            Category::Incomplete => "Incomplete downloads",
+            Category::Media => "Media",
+            Category::Document => "Documents",
+            Category::Folder => "Folders",
+            Category::Other => "Other",
+        }
+    }
+
+    /// Whether `--yes` may trash this category. Media and documents are often
+    /// kept on purpose, so they are only trashed after an explicit answer.
+    pub fn unattended(self) -> bool {
+        matches!(self, Category::Installer | Category::ExtractedArchive | Category::Incomplete)
+    }
+}
+
+pub struct Download {
+    pub path: PathBuf,
+    pub size: u64,
+    pub category: Category,
+    pub modified: SystemTime,
+}
+
+/// Groups the Downloads folder by type and age and moves whole categories to
+/// the Trash: installers, archives that were already extracted, stale partial
+/// downloads and, when asked, old archives, media and documents.
+pub async fn cleanup_downloads(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let dir = downloads_dir();
+    let downloads = triage(&dir);
+    if downloads.is_empty() {
+        println!("  {} {} is empty", style("ℹ").blue(), dir.display());
+        return Ok(0);
+    }
+
+    let days = min_age_days.unwrap_or(DEFAULT_MIN_AGE_DAYS);
+    print_summary(&downloads);
+
+    let bulk_actions = [
+        (Category::Incomplete, INCOMPLETE_MIN_AGE_DAYS),
+        (Category::ExtractedArchive, 0),
+        (Category::Installer, days),
+        (Category::Archive, days),
+        (Category::Media, days),
+        (Category::Document, days),
+    ];
+
+    let mut total_trashed = 0u64;
+    for (category, min_days) in bulk_actions {
+        let cutoff = days_ago(min_days);
+        let mut selected: Vec<&Download> = downloads
+            .iter()
+            .filter(|download| download.category == category && download.modified < cutoff)
+            .collect();
+        if selected.is_empty() {
+            continue;
+        }
+        selected.sort_by_key(|download| std::cmp::Reverse(download.size));
+
+        let name = if min_days == 0 {
+            category.label().to_lowercase()
+        } else {
+            format!("{} older than {} days", category.label().to_lowercase(), min_days)
+        };
+        let total: u64 = selected.iter().map(|download| download.size).sum();
+
+        println!("\n  {} {} ({} files, {})", style("📥").cyan(), name, selected.len(), format_size(total));
+        for download in selected.iter().take(10) {
+            println!("    {} {}",
+                style(download.path.file_name().unwrap_or_default().to_string_lossy()).dim(),
+                format_size(download.size)
+            );
+        }
+        if selected.len() > 10 {
+            println!("    {}", style(format!("... and {} more", selected.len() - 10)).dim());
+        }
+
+        if yes && !category.unattended() {
+            println!("  {} Skipping {} with --yes; run interactively to trash them", style("ℹ").blue(), name);
+            continue;
+        }
+
+        if dry_run {
+            println!("  Would move {} to the Trash: {}", name, format_size(total));
+            total_trashed += total;
+            continue;
+        }
+
+        if !yes && !Confirm::new()
+            .with_prompt(format!("Move {} ({} files, {}) to the Trash?", name, selected.len(), format_size(total)))
+            .default(false)
+            .interact()?
+        {
+            continue;
+        }
+
+        for download in selected {
+            match move_to_trash(&download.path) {
+                Ok(()) => total_trashed += download.size,
+                Err(e) => println!("    {} Could not trash {}: {}", style("✗").red(), download.path.display(), e),
+            }
+        }
+    }
+
+    Ok(total_trashed)
+}
+
+/// Every top-level entry of `dir` with its category.
+pub fn triage(dir: &Path) -> Vec<Download> {
+    let entries: Vec<fs::DirEntry> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .collect();
+    let folders: HashSet<String> = entries
+        .iter()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let (category, size) = if metadata.is_dir() {
+                (Category::Folder, calculate_directory_size(&entry.path()).unwrap_or(0))
+            } else {
+                (categorize(&name, &folders), metadata.len())
+            };
+            Some(Download {
+                path: entry.path(),
+                size,
+                category,
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            })
+        })
+        .collect()
+}
+
+fn categorize(name: &str, folders: &HashSet<String>) -> Category {
+    let lower = name.to_lowercase();
+    let extension = lower.rsplit_once('.').map(|(_, ext)| ext).unwrap_or_default();
+
+    if INCOMPLETE_EXTENSIONS.contains(&extension) {
+        Category::Incomplete
+    } else if INSTALLER_EXTENSIONS.contains(&extension) {
+        Category::Installer
+    } else if ARCHIVE_EXTENSIONS.contains(&extension) {
+        if folders.contains(archive_stem(name)) {
+            Category::ExtractedArchive
+        } else {
+            Category::Archive
+        }
+    } else if MEDIA_EXTENSIONS.contains(&extension) {
+        Category::Media
+    } else if DOCUMENT_EXTENSIONS.contains(&extension) {
+        Category::Document
+    } else {
+        Category::Other
+    }
+}
+
+/// `project-1.2.tar.gz` -> `project-1.2`, the folder it usually extracts to.
+fn archive_stem(name: &str) -> &str {
+    let stem = name.rsplit_once('.').map(|(stem, _)| stem).unwrap_or(name);
+    match stem.rsplit_once('.') {
+        Some((inner, ext)) if ext.eq_ignore_ascii_case("tar") => inner,
+        _ => stem,
+    }
+}
+
+/// Sizes per category and age bucket.
+pub fn print_summary(downloads: &[Download]) {
+    let now = SystemTime::now();
+    let age_days = |download: &Download| now.duration_since(download.modified).map(|age| age.as_secs() / 86400).unwrap_or(0);
+
+    print!("  {:<22} {:>7} {:>12}", "", "files", "total");
+    for (label, _) in AGE_BUCKETS {
+        print!(" {:>12}", label);
+    }
+    println!();
+
+    for category in Category::ALL {
+        let items: Vec<&Download> = downloads.iter().filter(|download| download.category == *category).collect();
+        if items.is_empty() {
+            continue;
+        }
+        print!("  {:<22} {:>7} {:>12}",
+            category.label(),
+            items.len(),
+            format_size(items.iter().map(|download| download.size).sum())
+        );
+
+        let mut lower = 0;
+        for (_, upper) in AGE_BUCKETS {
+            let size: u64 = items
+                .iter()
+                .filter(|download| (lower..*upper).contains(&age_days(download)))
+                .map(|download| download.size)
+                .sum();
+            print!(" {:>12}", if size > 0 { format_size(size) } else { "-".to_string() });
+            lower = *upper;
+        }
+        println!();
+    }
+}
+
+/// `XDG_DOWNLOAD_DIR` from `user-dirs.dirs`, or `~/Downloads`.
+pub fn downloads_dir() -> PathBuf {
+    let configured = fs::read_to_string(config_home().join("user-dirs.dirs")).ok().and_then(|dirs| {
+        dirs.lines().find_map(|line| {
+            let value = line.trim().strip_prefix("XDG_DOWNLOAD_DIR=")?.trim_matches('"');
+            Some(PathBuf::from(shellexpand::full(value).ok()?.as_ref()))
+        })
+    });
+
+    configured.unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/Downloads").as_ref()))
+}
+
+/// Moves `path` to the user's Trash: `~/.Trash` on macOS, the freedesktop.org
+/// Trash with a `.trashinfo` record elsewhere, so it can be restored. Files on
+/// another filesystem than the home Trash go to `$topdir/.Trash-$uid` on their
+/// own filesystem, as the specification allows, instead of being copied.
+fn move_to_trash(path: &Path) -> Result<()> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+
+    if cfg!(target_os = "macos") {
+        let trash = PathBuf::from(shellexpand::tilde("~/.Trash").as_ref());
+        let target = unused_name(&name, |candidate| trash.join(candidate).exists());
+        fs::rename(path, trash.join(target))?;
+        return Ok(());
+    }
+
+    let original = fs::canonicalize(path)?;
+    match trash_into(&data_home().join("Trash"), path, &name, &original) {
+        Err(e) if e.raw_os_error() == Some(libc::EXDEV) => {
+            // Paths in a top directory Trash are relative to the top directory
+            let topdir = mount_point(&original)?;
+            let trash = topdir.join(format!(".Trash-{}", effective_uid()));
+            if !trash.exists() {
+                fs::create_dir(&trash)?;
+                fs::set_permissions(&trash, fs::Permissions::from_mode(0o700))?;
+            }
+            trash_into(&trash, path, &name, original.strip_prefix(&topdir).unwrap_or(&original))?;
+            Ok(())
+        }
+        result => Ok(result?),
+    }
+}
+
+/// Moves `path` into the `files` directory of the Trash at `trash`, recording
+/// `original` in its `.trashinfo` file.
+fn trash_into(trash: &Path, path: &Path, name: &str, original: &Path) -> io::Result<()> {
+    let files = trash.join("files");
+    let info = trash.join("info");
+    fs::create_dir_all(&files)?;
+    fs::create_dir_all(&info)?;
+
+    let target = unused_name(name, |candidate| {
+        files.join(candidate).exists() || info.join(format!("{}.trashinfo", candidate)).exists()
+    });
+    // The info file goes first, as the specification asks
+    fs::write(
+        info.join(format!("{}.trashinfo", target)),
+        format!("[Trash Info]\nPath={}\nDeletionDate={}\n",
+            percent_encode(&original.to_string_lossy()),
+            Local::now().format("%Y-%m-%dT%H:%M:%S")
+        ),
+    )?;
+    if let Err(e) = fs::rename(path, files.join(&target)) {
+        let _ = fs::remove_file(info.join(format!("{}.trashinfo", target)));
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// The top directory of the filesystem `path` is on: its highest ancestor on
+/// the same device.
+fn mount_point(path: &Path) -> io::Result<PathBuf> {
+    let device = fs::metadata(path)?.dev();
+    let mut topdir = path;
+    while let Some(parent) = topdir.parent() {
+        if fs::metadata(parent)?.dev() != device {
+            break;
+        }
+        topdir = parent;
+    }
+    Ok(topdir.to_path_buf())
+}
+
+/// `name`, or `name.2`, `name.3`, ... if it is taken.
+fn unused_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(name) {
+        return name.to_string();
+    }
+    (2..).map(|n| format!("{}.{}", name, n)).find(|candidate| !taken(candidate)).unwrap_or_default()
+}
+
+fn percent_encode(path: &str) -> String {
+    path.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn archive_stem_drops_compound_extensions() {
+        assert_eq!(archive_stem("project-1.2.tar.gz"), "project-1.2");
+        assert_eq!(archive_stem("project-1.2.TAR.XZ"), "project-1.2");
+        assert_eq!(archive_stem("photos.zip"), "photos");
+        assert_eq!(archive_stem("notes.backup.7z"), "notes.backup");
+    }
+
+    #[test]
+    fn categorize_by_extension_and_extracted_folder() {
+        let folders: HashSet<String> = ["project-1.2".to_string()].into();
+
+        assert_eq!(categorize("project-1.2.tar.gz", &folders), Category::ExtractedArchive);
+        assert_eq!(categorize("other-3.0.tar.gz", &folders), Category::Archive);
+        assert_eq!(categorize("Setup.EXE", &folders), Category::Installer);
+        assert_eq!(categorize("video.mp4.crdownload", &folders), Category::Incomplete);
+        assert_eq!(categorize("IMG_0001.HEIC", &folders), Category::Media);
+        assert_eq!(categorize("invoice.pdf", &folders), Category::Document);
+        assert_eq!(categorize("Makefile", &folders), Category::Other);
+    }
+}
@@ -0,0 +1,197 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::Confirm;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::utils::{calculate_directory_size, format_size};
+
+pub struct FlatpakRuntime {
+    /// `id/arch/branch`, e.g. `org.gnome.Platform/x86_64/45`
+    pub reference: String,
+    /// `user` or `system`
+    pub installation: String,
+    pub size: u64,
+}
+
+const INSTALLATIONS: &[&str] = &["user", "system"];
+
+/// Lists the runtimes no installed app uses, then uninstalls what `flatpak
+/// uninstall --unused` considers unused, so flatpak's own dependency tracking
+/// decides which runtimes and extensions (GL drivers, codecs, themes) are
+/// still needed.
+pub async fn cleanup_flatpak_runtimes(dry_run: bool, yes: bool) -> Result<u64> {
+    let Some(runtimes) = unused_runtimes() else {
+        println!("  {} flatpak not found", style("ℹ").dim());
+        return Ok(0);
+    };
+
+    if runtimes.is_empty() {
+        println!("  {} No unused Flatpak runtimes", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    // Deployed files are hardlinked into the OSTree repo, so sizes are an upper bound
+    let total: u64 = runtimes.iter().map(|runtime| runtime.size).sum();
+    for runtime in &runtimes {
+        println!("    {:<55} {:<7} {:>12}", runtime.reference, runtime.installation, format_size(runtime.size));
+    }
+
+    if dry_run {
+        println!("  Would uninstall {} unused Flatpak runtimes: up to {}", runtimes.len(), format_size(total));
+        return Ok(total);
+    }
+
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Uninstall {} unused Flatpak runtimes? (up to {})", runtimes.len(), format_size(total)))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    let mut total_freed = 0u64;
+    for installation in INSTALLATIONS {
+        let in_installation: Vec<&FlatpakRuntime> = runtimes
+            .iter()
+            .filter(|runtime| runtime.installation == *installation)
+            .collect();
+        if in_installation.is_empty() {
+            continue;
+        }
+
+        let output = Command::new("flatpak")
+            .args(["uninstall", "--unused", "--noninteractive", &format!("--{}", installation)])
+            .output()?;
+
+        if output.status.success() {
+            total_freed += in_installation
+                .iter()
+                .filter(|runtime| runtime_size(installation, &runtime.reference) == 0)
+                .map(|runtime| runtime.size)
+                .sum::<u64>();
+        } else {
+            println!("  {} Could not uninstall unused {} runtimes: {}",
+                style("✗").red(),
+                installation,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+
+    Ok(total_freed)
+}
+
+/// Runtimes in the user and system installations that no installed app uses
+/// as its runtime or SDK, found with queries only. Extensions of installed
+/// runtimes and apps and pinned runtimes are left out, as flatpak keeps them
+/// too. Returns `None` when flatpak is unavailable.
+pub fn unused_runtimes() -> Option<Vec<FlatpakRuntime>> {
+    if !Command::new("flatpak").arg("--version").output().is_ok_and(|output| output.status.success()) {
+        return None;
+    }
+
+    // Apps may use runtimes from either installation
+    let mut used = HashSet::new();
+    for installation in INSTALLATIONS {
+        let apps = flatpak_lines(&["list", "--app", "--columns=ref", &format!("--{}", installation)]);
+        for app in &apps {
+            for option in ["--show-runtime", "--show-sdk"] {
+                used.extend(flatpak_lines(&["info", option, &format!("--{}", installation), app]));
+            }
+        }
+        used.extend(apps);
+    }
+
+    let mut unused = Vec::new();
+    for installation in INSTALLATIONS {
+        let installed = flatpak_lines(&["list", "--runtime", "--columns=ref", &format!("--{}", installation)]);
+        let pinned = flatpak_lines(&["pin", &format!("--{}", installation)]);
+        for reference in unused_refs(&installed, &used, &pinned) {
+            unused.push(FlatpakRuntime {
+                size: runtime_size(installation, &reference),
+                installation: installation.to_string(),
+                reference,
+            });
+        }
+    }
+
+    Some(unused)
+}
+
+/// The refs in `installed` that are not in `used`, not an extension (`.Locale`,
+/// `.GL.default`, plugins) of a used or another installed ref, and not pinned.
+/// Pins are patterns like `runtime/org.gnome.Platform/x86_64/45`.
+fn unused_refs(installed: &[String], used: &HashSet<String>, pinned: &[String]) -> Vec<String> {
+    let id = |reference: &str| reference.split('/').next().unwrap_or_default().to_string();
+    let parents: HashSet<String> = used.iter().chain(installed).map(|reference| id(reference)).collect();
+
+    installed
+        .iter()
+        .filter(|reference| !used.contains(*reference))
+        .filter(|reference| {
+            let id = id(reference);
+            !parents.iter().any(|parent| id.starts_with(&format!("{}.", parent)))
+        })
+        .filter(|reference| {
+            !pinned.iter().any(|pin| pin.trim_start_matches("runtime/") == reference.as_str())
+        })
+        .cloned()
+        .collect()
+}
+
+fn flatpak_lines(args: &[&str]) -> Vec<String> {
+    match Command::new("flatpak").args(args).output() {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Size of a deployed runtime, 0 once it is uninstalled.
+fn runtime_size(installation: &str, reference: &str) -> u64 {
+    let path = installation_dir(installation).join("runtime").join(reference);
+    calculate_directory_size(&path).unwrap_or(0)
+}
+
+fn installation_dir(installation: &str) -> PathBuf {
+    if installation == "user" {
+        PathBuf::from(shellexpand::tilde("~/.local/share/flatpak").as_ref())
+    } else {
+        PathBuf::from("/var/lib/flatpak")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unused_refs_keeps_used_extensions_and_pinned() {
+        let installed: Vec<String> = [
+            "org.gnome.Platform/x86_64/45",
+            "org.gnome.Platform/x86_64/44",
+            "org.gnome.Platform.Locale/x86_64/44",
+            "org.freedesktop.Platform/x86_64/23.08",
+            "org.freedesktop.Platform.GL.default/x86_64/23.08",
+            "org.kde.Platform/x86_64/5.15-23.08",
+            "org.example.App.Locale/x86_64/stable",
+        ]
+        .map(String::from)
+        .to_vec();
+        let used: HashSet<String> = ["org.example.App/x86_64/stable", "org.gnome.Platform/x86_64/45"]
+            .map(String::from)
+            .into();
+        let pinned = vec!["runtime/org.kde.Platform/x86_64/5.15-23.08".to_string()];
+
+        assert_eq!(
+            unused_refs(&installed, &used, &pinned),
+            vec!["org.gnome.Platform/x86_64/44", "org.freedesktop.Platform/x86_64/23.08"]
+        );
+    }
+}
@@ -0,0 +1,282 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::Confirm;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::utils::{calculate_directory_size, format_size};
+
+/// Loose objects beyond this make `git gc` worthwhile.
+const GC_LOOSE_OBJECTS: u64 = 1000;
+
+/// Pack files beyond this make `git gc` worthwhile.
+const GC_PACKS: u64 = 50;
+
+/// Only unreachable objects older than this are pruned, the same grace period
+/// `git gc` gives operations that are still in progress.
+const PRUNE_EXPIRE: &str = "--expire=2.weeks.ago";
+
+pub struct GitRepo {
+    pub path: PathBuf,
+    /// Total size of `.git`
+    pub size: u64,
+    pub loose_objects: u64,
+    pub loose_size: u64,
+    pub pack_size: u64,
+    pub packs: u64,
+    /// Temporary and otherwise unusable files in the object store
+    pub garbage_size: u64,
+    /// Unreachable loose objects `git prune` would remove. Only counted by the
+    /// cleaner, since it means walking the whole history.
+    pub unreachable: usize,
+    /// Worktrees whose directory is gone
+    pub stale_worktrees: Vec<String>,
+    pub lfs_size: u64,
+    /// LFS objects `git lfs prune` would remove. Only counted by the cleaner,
+    /// and only where git-lfs is installed.
+    pub lfs_prunable: usize,
+}
+
+impl GitRepo {
+    fn needs_gc(&self) -> bool {
+        self.loose_objects >= GC_LOOSE_OBJECTS || self.packs >= GC_PACKS || self.garbage_size > 0
+    }
+
+    /// The maintenance commands worth running, as git arguments.
+    fn actions(&self) -> Vec<Vec<&'static str>> {
+        let mut actions = Vec::new();
+        if !self.stale_worktrees.is_empty() {
+            actions.push(vec!["worktree", "prune"]);
+        }
+        // gc prunes unreachable objects itself
+        if self.needs_gc() {
+            actions.push(vec!["gc"]);
+        } else if self.unreachable > 0 {
+            actions.push(vec!["prune", PRUNE_EXPIRE]);
+        }
+        if self.lfs_prunable > 0 {
+            actions.push(vec!["lfs", "prune"]);
+        }
+        actions
+    }
+
+    /// What the actions can save at most: loose objects shrink when packed and
+    /// garbage is deleted. LFS savings are only known after pruning.
+    fn estimated_savings(&self) -> u64 {
+        let packing = if self.needs_gc() { self.loose_size / 2 } else { 0 };
+        packing + self.garbage_size
+    }
+}
+
+/// Reports the internals of every repository under the project roots and offers
+/// `git gc`, `prune`, `worktree prune` and `lfs prune` where they would help.
+pub async fn cleanup_git_repos(dry_run: bool, yes: bool) -> Result<u64> {
+    if Command::new("git").arg("--version").output().is_err() {
+        println!("  {} git not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let mut repos = find_repos(&crate::config::load()?.project_roots());
+    if repos.is_empty() {
+        println!("  {} No repositories found under the project roots", style("ℹ").blue());
+        return Ok(0);
+    }
+    repos.sort_by_key(|repo| std::cmp::Reverse(repo.size));
+    let has_lfs = Command::new("git").args(["lfs", "version"]).output().is_ok_and(|output| output.status.success());
+    for repo in &mut repos {
+        repo.unreachable = count_unreachable(&repo.path);
+        if has_lfs && repo.lfs_size > 0 {
+            repo.lfs_prunable = count_lfs_prunable(&repo.path);
+        }
+    }
+
+    println!("  {:<50} {:>10} {:>8} {:>10} {:>10}  notes", "Repository", ".git", "loose", "packs", "LFS");
+    for repo in repos.iter().take(15) {
+        println!("  {:<50} {:>10} {:>8} {:>10} {:>10}  {}",
+            shorten(&repo.path),
+            format_size(repo.size),
+            repo.loose_objects,
+            format_size(repo.pack_size),
+            format_size(repo.lfs_size),
+            style(notes(repo)).yellow()
+        );
+    }
+    if repos.len() > 15 {
+        println!("  {}", style(format!("... and {} more", repos.len() - 15)).dim());
+    }
+
+    let mut total_freed = 0u64;
+
+    for repo in &repos {
+        let actions = repo.actions();
+        if actions.is_empty() {
+            continue;
+        }
+        let commands: Vec<String> = actions.iter().map(|action| format!("git {}", action.join(" "))).collect();
+
+        if dry_run {
+            println!("  Would run {} in {}: up to {}", commands.join(", "), repo.path.display(), format_size(repo.estimated_savings()));
+            total_freed += repo.estimated_savings();
+            continue;
+        }
+
+        if !yes && !Confirm::new()
+            .with_prompt(format!("Run {} in {}? (.git is {})", commands.join(", "), repo.path.display(), format_size(repo.size)))
+            .default(false)
+            .interact()?
+        {
+            continue;
+        }
+
+        for action in &actions {
+            let output = Command::new("git").arg("-C").arg(&repo.path).args(action).output()?;
+            if !output.status.success() {
+                println!("  {} git {} failed in {}: {}",
+                    style("✗").red(),
+                    action.join(" "),
+                    repo.path.display(),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+        }
+
+        let size_after = calculate_directory_size(&repo.path.join(".git")).unwrap_or(repo.size);
+        println!("  {} {}: {} -> {}", style("✓").green(), repo.path.display(), format_size(repo.size), format_size(size_after));
+        total_freed += repo.size.saturating_sub(size_after);
+    }
+
+    Ok(total_freed)
+}
+
+/// Repositories (directories with a `.git` directory) under `roots`. Worktrees
+/// and submodules, whose `.git` is a file, belong to another repository.
+pub fn find_repos(roots: &[PathBuf]) -> Vec<GitRepo> {
+    let mut repos = Vec::new();
+
+    for root in roots {
+        for entry in crate::utils::walk_project_root(root) {
+            if entry.file_type().is_dir() && entry.path().join(".git").is_dir() {
+                repos.push(inspect_repo(entry.path()));
+            }
+        }
+    }
+
+    repos
+}
+
+fn inspect_repo(path: &Path) -> GitRepo {
+    let git_dir = path.join(".git");
+
+    // count-objects reports sizes in KiB
+    let counts = git_output(path, &["count-objects", "-v"]).unwrap_or_default();
+    let count = |key: &str| {
+        counts
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(": ")?.trim().parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+
+    // Porcelain output has one block per worktree; stale ones carry a `prunable` line
+    let worktrees = git_output(path, &["worktree", "list", "--porcelain"]).unwrap_or_default();
+    let stale_worktrees = worktrees
+        .split("\n\n")
+        .filter(|block| block.lines().any(|line| line.starts_with("prunable")))
+        .filter_map(|block| block.lines().next()?.strip_prefix("worktree ").map(String::from))
+        .collect();
+
+    GitRepo {
+        size: calculate_directory_size(&git_dir).unwrap_or(0),
+        loose_objects: count("count"),
+        loose_size: count("size") * 1024,
+        pack_size: count("size-pack") * 1024,
+        packs: count("packs"),
+        garbage_size: count("size-garbage") * 1024,
+        unreachable: 0,
+        stale_worktrees,
+        lfs_size: calculate_directory_size(&git_dir.join("lfs/objects")).unwrap_or(0),
+        lfs_prunable: 0,
+        path: path.to_path_buf(),
+    }
+}
+
+/// Unreachable loose objects old enough for `git prune` to remove.
+fn count_unreachable(repo: &Path) -> usize {
+    git_output(repo, &["prune", "--dry-run", PRUNE_EXPIRE])
+        .map(|output| output.lines().count())
+        .unwrap_or(0)
+}
+
+/// LFS objects old enough for `git lfs prune` to remove.
+fn count_lfs_prunable(repo: &Path) -> usize {
+    let Ok(output) = Command::new("git").arg("-C").arg(repo).args(["lfs", "prune", "--dry-run"]).output() else {
+        return 0;
+    };
+    if !output.status.success() {
+        return 0;
+    }
+    // The summary goes to stdout or stderr depending on the git-lfs version
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    parse_lfs_prunable(&stdout).max(parse_lfs_prunable(&stderr))
+}
+
+/// Reads the count from the `prune: N files would be pruned (size)` line. Nothing
+/// is printed when there is nothing to prune.
+fn parse_lfs_prunable(output: &str) -> usize {
+    output.lines()
+        .filter(|line| line.contains("would be pruned"))
+        .find_map(|line| line.trim_start_matches("prune:").split_whitespace().next()?.parse().ok())
+        .unwrap_or(0)
+}
+
+/// Short summary of what maintenance would do for `repo`.
+pub fn notes(repo: &GitRepo) -> String {
+    let mut notes = Vec::new();
+    if repo.needs_gc() {
+        notes.push("needs gc".to_string());
+    }
+    if repo.unreachable > 0 {
+        notes.push(format!("{} unreachable", repo.unreachable));
+    }
+    if repo.lfs_prunable > 0 {
+        notes.push(format!("{} prunable LFS objects", repo.lfs_prunable));
+    }
+    if !repo.stale_worktrees.is_empty() {
+        notes.push(format!("{} stale worktrees", repo.stale_worktrees.len()));
+    }
+    notes.join(", ")
+}
+
+fn git_output(repo: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").arg("-C").arg(repo).args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Paths relative to the home directory, and at most 50 characters wide.
+fn shorten(path: &Path) -> String {
+    let home = PathBuf::from(shellexpand::tilde("~").as_ref());
+    let display = match path.strip_prefix(&home) {
+        Ok(relative) => format!("~/{}", relative.display()),
+        Err(_) => path.display().to_string(),
+    };
+    let chars: Vec<char> = display.chars().collect();
+    if chars.len() > 50 {
+        format!("…{}", chars[chars.len() - 49..].iter().collect::<String>())
+    } else {
+        display
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lfs_prune_dry_run() {
+        let output = "prune: 12 local objects, 9 retained, done.\nprune: 3 files would be pruned (4.2 MB)\n";
+        assert_eq!(parse_lfs_prunable(output), 3);
+        assert_eq!(parse_lfs_prunable("prune: 1 file would be pruned (10 B)"), 1);
+        assert_eq!(parse_lfs_prunable("prune: 4 local objects, 4 retained, done.\n"), 0);
+        assert_eq!(parse_lfs_prunable(""), 0);
+    }
+}
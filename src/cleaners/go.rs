@@ -0,0 +1,152 @@
+use anyhow::Result;
+use console::style;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+use super::Removal;
+use crate::utils::{cache_home, calculate_directory_size, days_ago, format_size, last_used, last_used_in};
+
+/// Modules and build outputs unused for this long are trimmed by default.
+const DEFAULT_MAX_UNUSED_DAYS: u64 = 90;
+
+/// Trims the Go module cache (`GOMODCACHE`) and build cache (`GOCACHE`) by last
+/// use. Extracted modules are read-only, which `utils::remove_path` takes care of.
+pub async fn cleanup_go(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_UNUSED_DAYS);
+    let cutoff = days_ago(days);
+    let mut total_freed = 0u64;
+
+    let mod_cache = go_dir("GOMODCACHE").unwrap_or_else(|| gopath().join("pkg/mod"));
+    if mod_cache.exists() {
+        let modules = find_stale_modules(&mod_cache, cutoff);
+        println!("  {} Modules unused for {} days: {}",
+            style("📦").cyan(), days, format_size(modules.iter().map(|r| r.size).sum()));
+        total_freed += super::remove_with_confirmation("unused Go modules", &modules, dry_run, yes)?;
+    } else {
+        println!("  {} {} not found", style("ℹ").dim(), mod_cache.display());
+    }
+
+    let build_cache = go_dir("GOCACHE").unwrap_or_else(|| cache_home().join("go-build"));
+    if build_cache.exists() {
+        let outputs = find_stale_build_outputs(&build_cache, cutoff);
+        println!("  {} Build cache entries unused for {} days: {}",
+            style("🔨").cyan(), days, format_size(outputs.iter().map(|r| r.size).sum()));
+        total_freed += super::remove_with_confirmation("stale Go build cache entries", &outputs, dry_run, yes)?;
+    } else {
+        println!("  {} {} not found", style("ℹ").dim(), build_cache.display());
+    }
+
+    Ok(total_freed)
+}
+
+/// Resolves a Go directory setting from the environment, then from `go env`,
+/// which also reflects values written with `go env -w`.
+fn go_dir(name: &str) -> Option<PathBuf> {
+    if let Ok(dir) = env::var(name) {
+        if !dir.is_empty() {
+            return Some(PathBuf::from(dir));
+        }
+    }
+
+    let output = Command::new("go").args(["env", name]).output().ok()?;
+    let dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    // GOCACHE=off disables the build cache
+    (output.status.success() && !dir.is_empty() && dir != "off").then(|| PathBuf::from(dir))
+}
+
+fn gopath() -> PathBuf {
+    go_dir("GOPATH")
+        // GOPATH may list several directories; the module cache lives in the first
+        .and_then(|paths| env::split_paths(&paths).next())
+        .unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/go").as_ref()))
+}
+
+/// Extracted modules (`<module path>@<version>` directories) and their downloaded
+/// archives under `cache/download` that have not been used since `cutoff`.
+fn find_stale_modules(mod_cache: &Path, cutoff: SystemTime) -> Vec<Removal> {
+    let mut removals = Vec::new();
+
+    let mut entries = WalkDir::new(mod_cache).min_depth(1).into_iter();
+    while let Some(entry) = entries.next() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        if entry.depth() == 1 && entry.file_name() == "cache" {
+            entries.skip_current_dir();
+            continue;
+        }
+        if !entry.file_name().to_string_lossy().contains('@') {
+            continue;
+        }
+
+        entries.skip_current_dir();
+        if last_used_in(entry.path()) < cutoff {
+            removals.push(Removal {
+                description: module_name(mod_cache, entry.path()),
+                size: calculate_directory_size(entry.path()).unwrap_or(0),
+                path: entry.into_path(),
+            });
+        }
+    }
+
+    // cache/download/<module>/@v/<version>.{zip,mod,info,ziphash}
+    for entry in WalkDir::new(mod_cache.join("cache/download")).into_iter().filter_map(|e| e.ok()) {
+        let in_version_dir = entry.path().parent().is_some_and(|parent| parent.ends_with("@v"));
+        if !in_version_dir || !entry.file_type().is_file() {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else { continue };
+        if last_used(&metadata) < cutoff {
+            removals.push(Removal {
+                description: format!("cache/download/{}", module_name(&mod_cache.join("cache/download"), entry.path())),
+                size: metadata.len(),
+                path: entry.into_path(),
+            });
+        }
+    }
+
+    removals
+}
+
+/// Build cache entries (`<2 hex digits>/<hash>-a|-d`) not used since `cutoff`. Go
+/// refreshes an entry's mtime whenever a build reuses it.
+fn find_stale_build_outputs(build_cache: &Path, cutoff: SystemTime) -> Vec<Removal> {
+    WalkDir::new(build_cache)
+        .min_depth(2)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            // Skip fuzzing corpora and anything else that is not a hash bucket
+            let bucket = entry.path().parent().and_then(Path::file_name).unwrap_or_default().to_string_lossy();
+            bucket.len() == 2 && bucket.chars().all(|c| c.is_ascii_hexdigit())
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            (last_used(&metadata) < cutoff).then(|| Removal {
+                description: format!("GOCACHE {}", entry.file_name().to_string_lossy()),
+                size: metadata.len(),
+                path: entry.into_path(),
+            })
+        })
+        .collect()
+}
+
+/// Module paths are stored case-encoded (`!azure` for `Azure`); decode them for display.
+fn module_name(base: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(base).unwrap_or(path).to_string_lossy().into_owned();
+    let mut name = String::with_capacity(relative.len());
+    let mut chars = relative.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '!' => name.extend(chars.next().map(|c| c.to_ascii_uppercase())),
+            _ => name.push(c),
+        }
+    }
+    name
+}
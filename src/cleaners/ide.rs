@@ -0,0 +1,358 @@
+use anyhow::Result;
+use console::style;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::Removal;
+use crate::utils::{app_support_home, cache_home, calculate_directory_size, data_home, file_uri_to_path, format_size, version_key};
+
+/// VS Code and its forks as (name, user data directory name, extensions directory
+/// relative to the home directory).
+const VSCODE_EDITORS: &[(&str, &str, &str)] = &[
+    ("VS Code", "Code", ".vscode/extensions"),
+    ("VS Code Insiders", "Code - Insiders", ".vscode-insiders/extensions"),
+    ("VSCodium", "VSCodium", ".vscode-oss/extensions"),
+    ("Cursor", "Cursor", ".cursor/extensions"),
+];
+
+/// Electron cache directories inside a VS Code user data directory.
+const ELECTRON_CACHE_DIRS: &[&str] = &["Cache", "Code Cache", "GPUCache", "CachedExtensionVSIXs"];
+
+/// Runs the VS Code and JetBrains cleaners in turn.
+pub async fn cleanup_ide_caches(dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    total_freed += cleanup_vscode(dry_run, yes).await?;
+    total_freed += cleanup_jetbrains(dry_run, yes).await?;
+
+    Ok(total_freed)
+}
+
+/// For VS Code and its forks, removes Electron caches, compiled code for
+/// previous releases (`CachedData`), workspace state for folders that no longer
+/// exist and extension versions superseded by a newer install.
+pub async fn cleanup_vscode(dry_run: bool, yes: bool) -> Result<u64> {
+    let home = PathBuf::from(shellexpand::tilde("~").as_ref());
+    let mut total_freed = 0u64;
+    let mut found = false;
+
+    for (name, data_dir, extensions_dir) in VSCODE_EDITORS {
+        let user_data = app_support_home().join(data_dir);
+        let extensions = home.join(extensions_dir);
+        if !user_data.is_dir() && !extensions.is_dir() {
+            continue;
+        }
+        found = true;
+
+        let mut removals = Vec::new();
+        for dir in ELECTRON_CACHE_DIRS {
+            let path = user_data.join(dir);
+            if path.is_dir() {
+                removals.push(Removal {
+                    description: format!("{} {}", name, dir),
+                    size: calculate_directory_size(&path).unwrap_or(0),
+                    path,
+                });
+            }
+        }
+        removals.extend(stale_cached_data(&user_data.join("CachedData"), name));
+        removals.extend(orphaned_workspace_storage(&user_data.join("User/workspaceStorage"), name));
+        removals.extend(superseded_extensions(&extensions, name));
+
+        println!("  {} {}: {}", style("📝").cyan(), name, format_size(removals.iter().map(|r| r.size).sum()));
+        total_freed += super::remove_with_confirmation(&format!("{} caches (close {} first)", name, name), &removals, dry_run, yes)?;
+    }
+
+    if !found {
+        println!("  {} VS Code not found", style("ℹ").dim());
+    }
+
+    Ok(total_freed)
+}
+
+/// Removes the cache and log directories JetBrains IDEs leave behind for
+/// versions that have since been upgraded (`IntelliJIdea2022.3` once
+/// `IntelliJIdea2023.1` exists), and the logs of current versions. Current
+/// indexes in `caches` are kept since rebuilding them takes a long time.
+///
+/// Settings and plugins are only offered for products with no installation
+/// left, behind their own confirmation, and never removed unattended.
+pub async fn cleanup_jetbrains(dry_run: bool, yes: bool) -> Result<u64> {
+    let cache_roots: Vec<PathBuf> = jetbrains_cache_roots().into_iter().filter(|root| root.is_dir()).collect();
+    let settings_roots: Vec<PathBuf> = jetbrains_settings_roots().into_iter().filter(|root| root.is_dir()).collect();
+    if cache_roots.is_empty() && settings_roots.is_empty() {
+        println!("  {} JetBrains IDEs not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let caches = jetbrains_versions(&cache_roots);
+    let mut removals = Vec::new();
+    for (product, versions) in &caches {
+        let newest = versions.keys().max_by_key(|version| version_key(version)).cloned();
+
+        for (version, dirs) in versions {
+            if Some(version) == newest.as_ref() {
+                // Logs sit in log/ under the cache directory on Linux and in
+                // ~/Library/Logs/JetBrains/<product><version> on macOS
+                for dir in dirs {
+                    let log = if dir.starts_with(macos_logs_root()) { dir.clone() } else { dir.join("log") };
+                    if log.is_dir() {
+                        removals.push(Removal {
+                            description: format!("{}{} logs", product, version),
+                            size: calculate_directory_size(&log).unwrap_or(0),
+                            path: log,
+                        });
+                    }
+                }
+                continue;
+            }
+
+            for dir in dirs {
+                removals.push(Removal {
+                    description: format!("{}{} (superseded by {}) {}",
+                        product,
+                        version,
+                        newest.as_deref().unwrap_or_default(),
+                        dir.display()
+                    ),
+                    size: calculate_directory_size(dir).unwrap_or(0),
+                    path: dir.clone(),
+                });
+            }
+        }
+    }
+
+    println!("  {} JetBrains: {}", style("🧠").cyan(), format_size(removals.iter().map(|r| r.size).sum()));
+    let mut total_freed = super::remove_with_confirmation("old JetBrains caches and logs", &removals, dry_run, yes)?;
+
+    let installed = installed_jetbrains_products();
+    let mut orphaned = Vec::new();
+    for (product, versions) in jetbrains_versions(&settings_roots) {
+        if installed.contains(&product) || recently_used(caches.get(&product)) {
+            continue;
+        }
+        for (version, dirs) in versions {
+            for dir in dirs {
+                orphaned.push(Removal {
+                    description: format!("{}{} settings and plugins (not installed) {}", product, version, dir.display()),
+                    size: calculate_directory_size(&dir).unwrap_or(0),
+                    path: dir,
+                });
+            }
+        }
+    }
+
+    if !orphaned.is_empty() {
+        if yes && !dry_run {
+            println!("  {} Skipping settings of uninstalled JetBrains IDEs ({}); run without --yes to review them",
+                style("ℹ").dim(),
+                format_size(orphaned.iter().map(|r| r.size).sum())
+            );
+        } else {
+            total_freed += super::remove_with_confirmation("settings and plugins of uninstalled JetBrains IDEs", &orphaned, dry_run, false)?;
+        }
+    }
+
+    Ok(total_freed)
+}
+
+/// product -> version -> directories for that version across `roots`
+fn jetbrains_versions(roots: &[PathBuf]) -> HashMap<String, HashMap<String, Vec<PathBuf>>> {
+    let mut products: HashMap<String, HashMap<String, Vec<PathBuf>>> = HashMap::new();
+    for root in roots {
+        for entry in fs::read_dir(root).into_iter().flatten().flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Some((product, version)) = split_jetbrains_dir(&name) {
+                products
+                    .entry(product.to_string())
+                    .or_default()
+                    .entry(version.to_string())
+                    .or_default()
+                    .push(entry.path());
+            }
+        }
+    }
+    products
+}
+
+/// Whether any cache directory of a product changed in the last 90 days, in
+/// case the IDE is installed somewhere `installed_jetbrains_products` doesn't look.
+fn recently_used(versions: Option<&HashMap<String, Vec<PathBuf>>>) -> bool {
+    let cutoff = crate::utils::days_ago(90);
+    versions
+        .into_iter()
+        .flat_map(|versions| versions.values().flatten())
+        .filter_map(|dir| fs::metadata(dir).and_then(|metadata| metadata.modified()).ok())
+        .any(|modified| modified > cutoff)
+}
+
+/// Products with an installation in the usual places (Toolbox, `/opt`,
+/// `/snap`, `/Applications`, or unpacked in the home directory), read from the
+/// `dataDirectoryName` in each installation's `product-info.json`.
+fn installed_jetbrains_products() -> HashSet<String> {
+    let home = PathBuf::from(shellexpand::tilde("~").as_ref());
+    let mut places = vec![
+        (app_support_home().join("JetBrains/Toolbox/apps"), 6),
+        (data_home().join("JetBrains/Toolbox/apps"), 6),
+        (home.clone(), 2),
+    ];
+    if cfg!(target_os = "macos") {
+        places.push((PathBuf::from("/Applications"), 4));
+        places.push((home.join("Applications"), 4));
+    } else {
+        places.push((PathBuf::from("/opt"), 3));
+        places.push((PathBuf::from("/usr/local"), 3));
+        places.push((PathBuf::from("/snap"), 3));
+    }
+
+    let mut installed = HashSet::new();
+    for (place, depth) in places {
+        for entry in walkdir::WalkDir::new(&place).max_depth(depth).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_name() != "product-info.json" {
+                continue;
+            }
+            let Some(info) = fs::read_to_string(entry.path())
+                .ok()
+                .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+            else {
+                continue;
+            };
+            if let Some((product, _)) = info["dataDirectoryName"].as_str().and_then(split_jetbrains_dir) {
+                installed.insert(product.to_string());
+            }
+        }
+    }
+    installed
+}
+
+/// Compiled code for earlier releases: `CachedData` holds one directory per
+/// commit, and only the most recently used one belongs to the installed version.
+fn stale_cached_data(cached_data: &Path, editor: &str) -> Vec<Removal> {
+    let mut commits: Vec<(SystemTime, PathBuf)> = fs::read_dir(cached_data)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| (crate::utils::last_used_in(&entry.path()), entry.path()))
+        .collect();
+    commits.sort_by_key(|(used, _)| std::cmp::Reverse(*used));
+
+    commits
+        .into_iter()
+        .skip(1)
+        .map(|(_, path)| Removal {
+            description: format!("{} CachedData/{}", editor, path.file_name().unwrap_or_default().to_string_lossy()),
+            size: calculate_directory_size(&path).unwrap_or(0),
+            path,
+        })
+        .collect()
+}
+
+/// Workspace storage whose `workspace.json` points at a local folder or
+/// `.code-workspace` file that no longer exists. Remote workspaces are kept.
+fn orphaned_workspace_storage(storage: &Path, editor: &str) -> Vec<Removal> {
+    fs::read_dir(storage)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let contents = fs::read_to_string(entry.path().join("workspace.json")).ok()?;
+            let workspace: serde_json::Value = serde_json::from_str(&contents).ok()?;
+            let uri = workspace["folder"].as_str().or_else(|| workspace["workspace"].as_str())?;
+            let folder = file_uri_to_path(uri)?;
+            if folder.exists() {
+                return None;
+            }
+            Some(Removal {
+                description: format!("{} workspace state for {}", editor, folder.display()),
+                size: calculate_directory_size(&entry.path()).unwrap_or(0),
+                path: entry.path(),
+            })
+        })
+        .collect()
+}
+
+/// Extension directories (`publisher.name-1.2.3[-platform]`) for which a newer
+/// version of the same extension is installed, unless `extensions.json` still
+/// refers to them.
+fn superseded_extensions(extensions: &Path, editor: &str) -> Vec<Removal> {
+    let referenced: HashSet<String> = fs::read_to_string(extensions.join("extensions.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<Vec<serde_json::Value>>(&contents).ok())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|extension| extension["relativeLocation"].as_str().map(String::from))
+        .collect();
+
+    let mut installed: HashMap<String, Vec<(Vec<u64>, String)>> = HashMap::new();
+    for entry in fs::read_dir(extensions).into_iter().flatten().flatten() {
+        let dir = entry.file_name().to_string_lossy().into_owned();
+        if let Some((id, version)) = split_extension_dir(&dir) {
+            installed.entry(id.to_lowercase()).or_default().push((version_key(version), dir));
+        }
+    }
+
+    let mut removals = Vec::new();
+    for (id, mut versions) in installed {
+        versions.sort_by(|a, b| b.0.cmp(&a.0));
+        let newest = versions[0].0.clone();
+        for (version, dir) in versions {
+            if version < newest && !referenced.contains(&dir) {
+                let path = extensions.join(&dir);
+                removals.push(Removal {
+                    description: format!("{} extension {} ({})", editor, id, dir),
+                    size: calculate_directory_size(&path).unwrap_or(0),
+                    path,
+                });
+            }
+        }
+    }
+
+    removals
+}
+
+/// `ms-python.python-2024.2.1-linux-x64` -> (`ms-python.python`, `2024.2.1`)
+fn split_extension_dir(dir: &str) -> Option<(&str, &str)> {
+    // The version starts at the first dash followed by a digit after the publisher
+    let dot = dir.find('.')?;
+    let dash = dot + dir[dot..].match_indices('-').find(|(i, _)| {
+        dir[dot + i + 1..].starts_with(|c: char| c.is_ascii_digit())
+    })?.0;
+    let rest = &dir[dash + 1..];
+    let version = rest.split('-').next()?;
+    Some((&dir[..dash], version))
+}
+
+/// `IntelliJIdea2023.1` -> (`IntelliJIdea`, `2023.1`)
+fn split_jetbrains_dir(name: &str) -> Option<(&str, &str)> {
+    let start = name.find(|c: char| c.is_ascii_digit())?;
+    let (product, version) = name.split_at(start);
+    let is_version = version.len() >= 6
+        && version.starts_with("20")
+        && version.chars().all(|c| c.is_ascii_digit() || c == '.');
+    (!product.is_empty() && is_version).then_some((product, version))
+}
+
+/// Where JetBrains keeps per-version caches and logs.
+fn jetbrains_cache_roots() -> Vec<PathBuf> {
+    let mut roots = vec![cache_home().join("JetBrains")];
+    if cfg!(target_os = "macos") {
+        roots.push(macos_logs_root());
+    }
+    roots
+}
+
+/// Where JetBrains keeps per-version settings and plugins.
+fn jetbrains_settings_roots() -> Vec<PathBuf> {
+    let mut roots = vec![app_support_home().join("JetBrains")];
+    if !cfg!(target_os = "macos") {
+        roots.push(data_home().join("JetBrains"));
+    }
+    roots
+}
+
+fn macos_logs_root() -> PathBuf {
+    PathBuf::from(shellexpand::tilde("~/Library/Logs/JetBrains").as_ref())
+}
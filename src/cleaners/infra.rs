@@ -0,0 +1,482 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::Confirm;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::SystemTime;
+
+use super::Removal;
+use crate::utils::{cache_home, calculate_directory_size, config_home, days_ago, file_name, format_size, last_used, last_used_in, read_dirs, version_key};
+
+/// Entries unused for this long are removed by default.
+const DEFAULT_MAX_UNUSED_DAYS: u64 = 30;
+
+/// sccache's own default cache size, used when neither `SCCACHE_CACHE_SIZE`
+/// nor its configuration file sets one.
+const SCCACHE_DEFAULT_SIZE: u64 = 10 * 1024 * 1024 * 1024;
+
+/// Runs the Terraform, Helm, kubectl, Bazel, ccache and sccache cleaners in turn.
+pub async fn cleanup_infra_caches(min_age_days: Option<u64>, max_cache_size: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    total_freed += cleanup_terraform(min_age_days, dry_run, yes).await?;
+    total_freed += cleanup_helm(min_age_days, dry_run, yes).await?;
+    total_freed += cleanup_kubectl(min_age_days, dry_run, yes).await?;
+    total_freed += cleanup_bazel(dry_run, yes).await?;
+    total_freed += cleanup_ccache(max_cache_size, dry_run, yes).await?;
+    total_freed += cleanup_sccache(max_cache_size, dry_run, yes).await?;
+
+    Ok(total_freed)
+}
+
+/// Removes provider versions in the plugin cache that no project's
+/// `.terraform.lock.hcl` pins and that are neither the newest version nor used
+/// within `min_age_days`, plus project `.terraform` directories untouched for
+/// that long. `terraform init` recreates both.
+pub async fn cleanup_terraform(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_UNUSED_DAYS);
+    let cutoff = days_ago(days);
+    let roots = crate::config::load()?.project_roots();
+
+    let mut locked = HashSet::new();
+    let mut project_dirs = Vec::new();
+    for root in &roots {
+        for entry in crate::utils::walk_project_root(root) {
+            let name = entry.file_name();
+            if name == ".terraform.lock.hcl" {
+                locked.extend(locked_providers(&fs::read_to_string(entry.path()).unwrap_or_default()));
+            } else if name == ".terraform" && entry.file_type().is_dir() {
+                project_dirs.push(entry.into_path());
+            }
+        }
+    }
+
+    let plugin_cache = terraform_plugin_cache();
+    if plugin_cache.is_none() && project_dirs.is_empty() {
+        println!("  {} Terraform not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let mut removals = Vec::new();
+
+    // <host>/<namespace>/<type>/<version>/<os_arch>
+    if let Some(cache) = &plugin_cache {
+        for host in read_dirs(cache) {
+            for namespace in read_dirs(&host) {
+                for provider in read_dirs(&namespace) {
+                    let source = format!("{}/{}/{}", file_name(&host), file_name(&namespace), file_name(&provider));
+                    let versions = read_dirs(&provider);
+                    let newest = versions.iter().map(|version| file_name(version)).max_by_key(|version| version_key(version));
+
+                    for version in &versions {
+                        let name = file_name(version);
+                        let pinned = locked.contains(&format!("{}/{}", source, name));
+                        if pinned || Some(&name) == newest.as_ref() || last_used_in(version) >= cutoff {
+                            continue;
+                        }
+                        removals.push(Removal {
+                            description: format!("plugin-cache {} {}", source, name),
+                            size: calculate_directory_size(version).unwrap_or(0),
+                            path: version.clone(),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    for dir in project_dirs {
+        if last_used_in(&dir) < cutoff {
+            removals.push(Removal {
+                description: dir.display().to_string(),
+                size: calculate_directory_size(&dir).unwrap_or(0),
+                path: dir,
+            });
+        }
+    }
+
+    println!("  {} Terraform providers and .terraform dirs unused for {} days: {}",
+        style("🏗️").cyan(), days, format_size(removals.iter().map(|r| r.size).sum()));
+    super::remove_with_confirmation("Terraform caches", &removals, dry_run, yes)
+}
+
+/// Removes index files of Helm repositories that are no longer configured and
+/// chart archives not used within `min_age_days`.
+pub async fn cleanup_helm(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let repository_cache = match env::var("HELM_REPOSITORY_CACHE") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => match env::var("HELM_CACHE_HOME") {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir).join("repository"),
+            _ => cache_home().join("helm/repository"),
+        },
+    };
+    if !repository_cache.is_dir() {
+        println!("  {} Helm cache not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_UNUSED_DAYS);
+    let cutoff = days_ago(days);
+    let repositories = helm_repositories();
+    let mut removals = Vec::new();
+
+    for entry in fs::read_dir(&repository_cache).into_iter().flatten().flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let Ok(metadata) = entry.metadata() else { continue };
+        if !metadata.is_file() {
+            continue;
+        }
+
+        // `helm repo update` writes <repo>-index.yaml and <repo>-charts.txt
+        let repository = name.strip_suffix("-index.yaml").or_else(|| name.strip_suffix("-charts.txt"));
+        let reason = match repository {
+            Some(repository) if !repositories.contains(repository) => "repository removed",
+            Some(_) => continue,
+            None if name.ends_with(".tgz") && last_used(&metadata) < cutoff => "unused chart",
+            None => continue,
+        };
+        removals.push(Removal {
+            description: format!("{} ({})", name, reason),
+            size: metadata.len(),
+            path: entry.path(),
+        });
+    }
+
+    println!("  {} Helm cache: {}", style("⎈").cyan(), format_size(removals.iter().map(|r| r.size).sum()));
+    super::remove_with_confirmation("Helm cache", &removals, dry_run, yes)
+}
+
+/// Removes kubectl's API discovery and HTTP cache entries not used within
+/// `min_age_days`; kubectl refetches them from the cluster when needed.
+pub async fn cleanup_kubectl(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let cache = match env::var("KUBECACHEDIR") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(shellexpand::tilde("~/.kube/cache").as_ref()),
+    };
+    if !cache.is_dir() {
+        println!("  {} kubectl cache not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_UNUSED_DAYS);
+    let cutoff = days_ago(days);
+    let mut removals = Vec::new();
+
+    // discovery/ has one directory per API server, http/ one file per response
+    for kind in ["discovery", "http"] {
+        for entry in fs::read_dir(cache.join(kind)).into_iter().flatten().flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else { continue };
+            let (used, size) = if metadata.is_dir() {
+                (last_used_in(&path), calculate_directory_size(&path).unwrap_or(0))
+            } else {
+                (last_used(&metadata), metadata.len())
+            };
+            if used < cutoff {
+                removals.push(Removal {
+                    description: format!("{}/{}", kind, entry.file_name().to_string_lossy()),
+                    path,
+                    size,
+                });
+            }
+        }
+    }
+
+    println!("  {} kubectl cache unused for {} days: {}",
+        style("☸").cyan(), days, format_size(removals.iter().map(|r| r.size).sum()));
+    super::remove_with_confirmation("kubectl cache", &removals, dry_run, yes)
+}
+
+/// Removes Bazel output bases whose workspace directory no longer exists. The
+/// shared install base and repository cache are kept.
+pub async fn cleanup_bazel(dry_run: bool, yes: bool) -> Result<u64> {
+    let user = env::var("USER").unwrap_or_default();
+    let output_user_root = if cfg!(target_os = "macos") {
+        PathBuf::from(format!("/private/var/tmp/_bazel_{}", user))
+    } else {
+        cache_home().join(format!("bazel/_bazel_{}", user))
+    };
+    if !output_user_root.is_dir() {
+        println!("  {} Bazel output bases not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let mut removals = Vec::new();
+    for output_base in read_dirs(&output_user_root) {
+        // Output bases are named after the MD5 of the workspace path and record
+        // that path in DO_NOT_BUILD_HERE
+        let Ok(workspace) = fs::read_to_string(output_base.join("DO_NOT_BUILD_HERE")) else { continue };
+        let workspace = PathBuf::from(workspace.trim());
+        if workspace.exists() {
+            continue;
+        }
+        removals.push(Removal {
+            description: format!("output base of {} (workspace removed)", workspace.display()),
+            size: calculate_directory_size(&output_base).unwrap_or(0),
+            path: output_base,
+        });
+    }
+
+    println!("  {} Bazel output bases of removed workspaces: {}",
+        style("🧱").cyan(), format_size(removals.iter().map(|r| r.size).sum()));
+    super::remove_with_confirmation("Bazel output bases", &removals, dry_run, yes)
+}
+
+/// Trims ccache down to `max_size` bytes, or to its own configured `max_size`,
+/// with `ccache --cleanup`, evicting the least recently used results rather
+/// than wiping the cache.
+pub async fn cleanup_ccache(max_size: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let Some(cache_dir) = command_output("ccache", &["-k", "cache_dir"]).map(PathBuf::from) else {
+        println!("  {} ccache not found", style("ℹ").dim());
+        return Ok(0);
+    };
+
+    // ccache sizes without a unit are in gigabytes, and 0 means unlimited
+    let configured = command_output("ccache", &["-k", "max_size"]).and_then(|size| parse_cache_size(&size, 1000, 3));
+    let Some(limit) = max_size.or(configured).filter(|limit| *limit > 0) else {
+        println!("  {} ccache has no size limit", style("ℹ").dim());
+        return Ok(0);
+    };
+    let size = calculate_directory_size(&cache_dir).unwrap_or(0);
+    println!("  {} ccache: {} (limit {})", style("⚙️").cyan(), format_size(size), format_size(limit));
+    if size <= limit {
+        return Ok(0);
+    }
+
+    let excess = size - limit;
+    if dry_run {
+        println!("  Would trim ccache to {}: {}", format_size(limit), format_size(excess));
+        return Ok(excess);
+    }
+
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Trim ccache from {} to {}?", format_size(size), format_size(limit)))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    // CCACHE_MAXSIZE applies to this run only and leaves the configuration alone
+    let output = Command::new("ccache")
+        .arg("--cleanup")
+        .env("CCACHE_MAXSIZE", format!("{}Ki", limit / 1024))
+        .output()?;
+    if !output.status.success() {
+        println!("  {} ccache --cleanup failed: {}", style("✗").red(), String::from_utf8_lossy(&output.stderr).trim());
+    }
+
+    Ok(size.saturating_sub(calculate_directory_size(&cache_dir).unwrap_or(size)))
+}
+
+/// Trims sccache's local disk cache down to `max_size` bytes, or to its own
+/// configured size, by removing the least recently used entries. The sccache
+/// server is stopped once the removal is confirmed since it keeps its own
+/// account of the cache size.
+pub async fn cleanup_sccache(max_size: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let cache_dir = match env::var("SCCACHE_DIR") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ if cfg!(target_os = "macos") => cache_home().join("Mozilla.sccache"),
+        _ => cache_home().join("sccache"),
+    };
+    if !cache_dir.is_dir() {
+        println!("  {} sccache cache not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let mut entries: Vec<(SystemTime, u64, PathBuf)> = walkdir::WalkDir::new(&cache_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((last_used(&metadata), metadata.len(), entry.into_path()))
+        })
+        .collect();
+
+    let limit = max_size.unwrap_or_else(sccache_cache_size);
+    let size: u64 = entries.iter().map(|(_, size, _)| size).sum();
+    println!("  {} sccache: {} (limit {})", style("⚙️").cyan(), format_size(size), format_size(limit));
+    if size <= limit {
+        return Ok(0);
+    }
+
+    entries.sort_by_key(|(used, _, _)| *used);
+    let mut remaining = size;
+    let mut removals = Vec::new();
+    for (_, entry_size, path) in entries {
+        if remaining <= limit {
+            break;
+        }
+        remaining -= entry_size;
+        removals.push(Removal {
+            description: path.strip_prefix(&cache_dir).unwrap_or(&path).display().to_string(),
+            size: entry_size,
+            path,
+        });
+    }
+
+    super::remove_with_confirmation_after(
+        &format!("least recently used sccache entries (down to {})", format_size(limit)),
+        &removals,
+        dry_run,
+        yes,
+        || {
+            let _ = Command::new("sccache").arg("--stop-server").output();
+        },
+    )
+}
+
+/// The cache size from `SCCACHE_CACHE_SIZE` or `cache.disk.size` in sccache's
+/// configuration file, or sccache's default.
+fn sccache_cache_size() -> u64 {
+    if let Some(size) = env::var("SCCACHE_CACHE_SIZE").ok().and_then(|size| parse_cache_size(&size, 1024, 0)) {
+        return size;
+    }
+
+    let config = match env::var("SCCACHE_CONF") {
+        Ok(file) if !file.is_empty() => PathBuf::from(file),
+        _ if cfg!(target_os = "macos") => PathBuf::from(shellexpand::tilde("~/Library/Application Support/Mozilla.sccache/config").as_ref()),
+        _ => config_home().join("sccache/config"),
+    };
+    fs::read_to_string(config)
+        .ok()
+        .and_then(|config| config.parse::<toml::Table>().ok())
+        .and_then(|config| config.get("cache")?.get("disk")?.get("size")?.as_integer())
+        .and_then(|size| u64::try_from(size).ok())
+        .unwrap_or(SCCACHE_DEFAULT_SIZE)
+}
+
+/// Sizes like `5.0G`, `500 MiB` or `10G`. A trailing `i` makes the unit
+/// binary, otherwise units are powers of `base`; a bare number counts in units
+/// of `base^unitless_exponent`.
+fn parse_cache_size(size: &str, base: u64, unitless_exponent: u32) -> Option<u64> {
+    let size: String = size.chars().filter(|c| !c.is_whitespace()).collect();
+    let size = size.trim_end_matches('B');
+    let (size, base) = match size.strip_suffix('i') {
+        Some(size) => (size, 1024),
+        None => (size, base),
+    };
+    let split = size.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(size.len());
+    let (number, unit) = size.split_at(split);
+    let exponent = match unit {
+        "" => unitless_exponent,
+        "k" | "K" => 1,
+        "M" => 2,
+        "G" => 3,
+        "T" => 4,
+        _ => return None,
+    };
+    let number: f64 = number.parse().ok()?;
+    Some((number * base.pow(exponent) as f64) as u64)
+}
+
+/// `<source>/<version>` for every provider in a `.terraform.lock.hcl`.
+fn locked_providers(lock_file: &str) -> Vec<String> {
+    let mut providers = Vec::new();
+    let mut source = None;
+    for line in lock_file.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("provider \"") {
+            source = rest.split('"').next().map(String::from);
+        } else if let (Some(current), Some(rest)) = (&source, line.strip_prefix("version")) {
+            let version = rest.trim_start().trim_start_matches('=').trim().trim_matches('"');
+            providers.push(format!("{}/{}", current, version));
+            source = None;
+        }
+    }
+    providers
+}
+
+/// The provider plugin cache from `TF_PLUGIN_CACHE_DIR` or the CLI configuration.
+fn terraform_plugin_cache() -> Option<PathBuf> {
+    let configured = match env::var("TF_PLUGIN_CACHE_DIR") {
+        Ok(dir) if !dir.is_empty() => Some(dir),
+        _ => {
+            let config_file = match env::var("TF_CLI_CONFIG_FILE") {
+                Ok(file) if !file.is_empty() => PathBuf::from(file),
+                _ => PathBuf::from(shellexpand::tilde("~/.terraformrc").as_ref()),
+            };
+            fs::read_to_string(config_file).ok().and_then(|config| {
+                config.lines().find_map(|line| {
+                    let value = line.trim().strip_prefix("plugin_cache_dir")?.trim_start().strip_prefix('=')?;
+                    Some(value.trim().trim_matches('"').to_string())
+                })
+            })
+        }
+    };
+
+    let dir = match configured {
+        Some(dir) => PathBuf::from(shellexpand::full(&dir).map(|dir| dir.into_owned()).unwrap_or(dir)),
+        None => PathBuf::from(shellexpand::tilde("~/.terraform.d/plugin-cache").as_ref()),
+    };
+    dir.is_dir().then_some(dir)
+}
+
+/// Names of the repositories in Helm's `repositories.yaml`.
+fn helm_repositories() -> HashSet<String> {
+    let config = match env::var("HELM_REPOSITORY_CONFIG") {
+        Ok(file) if !file.is_empty() => PathBuf::from(file),
+        _ => match env::var("HELM_CONFIG_HOME") {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir).join("repositories.yaml"),
+            _ if cfg!(target_os = "macos") => PathBuf::from(shellexpand::tilde("~/Library/Preferences/helm/repositories.yaml").as_ref()),
+            _ => config_home().join("helm/repositories.yaml"),
+        },
+    };
+
+    // Entries look like `- name: bitnami` or `  name: bitnami` under `repositories:`
+    fs::read_to_string(config)
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.trim().trim_start_matches("- ").strip_prefix("name:"))
+        .map(|name| name.trim().trim_matches('"').to_string())
+        .collect()
+}
+
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !stdout.is_empty()).then_some(stdout)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locked_providers_pairs_sources_with_versions() {
+        let lock_file = r#"
+# This file is maintained automatically by "terraform init".
+provider "registry.terraform.io/hashicorp/aws" {
+  version     = "5.31.0"
+  constraints = "~> 5.0"
+  hashes = [
+    "h1:abc=",
+  ]
+}
+
+provider "registry.terraform.io/hashicorp/random" {
+  version = "3.6.0"
+}
+"#;
+        assert_eq!(locked_providers(lock_file), vec![
+            "registry.terraform.io/hashicorp/aws/5.31.0",
+            "registry.terraform.io/hashicorp/random/3.6.0",
+        ]);
+        assert!(locked_providers("").is_empty());
+    }
+
+    #[test]
+    fn parse_cache_size_handles_units() {
+        assert_eq!(parse_cache_size("5.0G", 1000, 3), Some(5_000_000_000));
+        assert_eq!(parse_cache_size("5.0 GiB", 1000, 3), Some(5 * 1024 * 1024 * 1024));
+        assert_eq!(parse_cache_size("5", 1000, 3), Some(5_000_000_000));
+        assert_eq!(parse_cache_size("0", 1000, 3), Some(0));
+        assert_eq!(parse_cache_size("10G", 1024, 0), Some(10 * 1024 * 1024 * 1024));
+        assert_eq!(parse_cache_size("2048", 1024, 0), Some(2048));
+        assert_eq!(parse_cache_size("lots", 1024, 0), None);
+    }
+}
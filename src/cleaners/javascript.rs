@@ -0,0 +1,179 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::Confirm;
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::utils::{cache_home, calculate_directory_size, data_home, env_dir, format_size};
+
+/// Runs the Yarn, pnpm, Bun and Deno cleaners in turn.
+pub async fn cleanup_js_caches(dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    println!("  {} Yarn cache", style("🧶").cyan());
+    total_freed += cleanup_yarn(dry_run, yes).await?;
+    println!("  {} pnpm store", style("📦").cyan());
+    total_freed += cleanup_pnpm(dry_run, yes).await?;
+    println!("  {} Bun install cache", style("🥟").cyan());
+    total_freed += cleanup_bun(dry_run, yes).await?;
+    println!("  {} Deno cache", style("🦕").cyan());
+    total_freed += cleanup_deno(dry_run, yes).await?;
+
+    Ok(total_freed)
+}
+
+/// Cleans the Yarn classic cache and the Yarn Berry global cache.
+pub async fn cleanup_yarn(dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    // Yarn classic keeps a versioned cache (v6/) under the user cache directory
+    let classic_dir = env_dir("YARN_CACHE_FOLDER")
+        .or_else(|| {
+            tool_output("yarn", &["cache", "dir"])
+                .map(PathBuf::from)
+                .filter(|dir| !dir.starts_with(home().join(".yarn/berry")))
+        })
+        .unwrap_or_else(|| {
+            if cfg!(target_os = "macos") { cache_home().join("Yarn") } else { cache_home().join("yarn") }
+        });
+    let classic_command = is_yarn_classic().then_some(("yarn", &["cache", "clean"][..]));
+    total_freed += clean_cache_dir("Yarn classic cache", classic_dir, classic_command, dry_run, yes)?;
+
+    // Berry shares one global cache across projects unless enableGlobalCache is off;
+    // its `yarn cache clean` only works inside a project, so the directory is removed
+    let berry_dir = env_dir("YARN_GLOBAL_FOLDER")
+        .unwrap_or_else(|| home().join(".yarn/berry"))
+        .join("cache");
+    total_freed += clean_cache_dir("Yarn Berry cache", berry_dir, None, dry_run, yes)?;
+
+    Ok(total_freed)
+}
+
+/// Prunes the pnpm content-addressable store. Only `pnpm store prune` knows which
+/// packages are still referenced by projects, so without pnpm nothing is removed.
+pub async fn cleanup_pnpm(dry_run: bool, yes: bool) -> Result<u64> {
+    let store = tool_output("pnpm", &["store", "path"])
+        .map(PathBuf::from)
+        .or_else(|| {
+            let default = if cfg!(target_os = "macos") {
+                home().join("Library/pnpm/store")
+            } else {
+                data_home().join("pnpm/store")
+            };
+            default.exists().then_some(default)
+        });
+
+    let Some(store) = store else {
+        println!("    {} pnpm store not found", style("ℹ").dim());
+        return Ok(0);
+    };
+
+    if tool_output("pnpm", &["--version"]).is_none() {
+        println!("    {} pnpm is not installed; the store at {} ({}) can only be pruned by pnpm",
+            style("ℹ").blue(),
+            store.display(),
+            format_size(calculate_directory_size(&store).unwrap_or(0))
+        );
+        return Ok(0);
+    }
+
+    // `pnpm store prune` has no dry run, so report the store size as the upper bound
+    if dry_run {
+        let size = calculate_directory_size(&store).unwrap_or(0);
+        println!("    Would run `pnpm store prune` on {}: up to {}", store.display(), format_size(size));
+        return Ok(size);
+    }
+
+    clean_cache_dir("pnpm store", store, Some(("pnpm", &["store", "prune"])), dry_run, yes)
+}
+
+/// Cleans Bun's global install cache.
+pub async fn cleanup_bun(dry_run: bool, yes: bool) -> Result<u64> {
+    let cache_dir = env_dir("BUN_INSTALL_CACHE_DIR").unwrap_or_else(|| {
+        env_dir("BUN_INSTALL")
+            .unwrap_or_else(|| home().join(".bun"))
+            .join("install/cache")
+    });
+    let command = tool_output("bun", &["--version"]).map(|_| ("bun", &["pm", "cache", "rm"][..]));
+
+    clean_cache_dir("Bun install cache", cache_dir, command, dry_run, yes)
+}
+
+/// Cleans Deno's module and compilation cache (`DENO_DIR`).
+pub async fn cleanup_deno(dry_run: bool, yes: bool) -> Result<u64> {
+    let deno_dir = env_dir("DENO_DIR").unwrap_or_else(|| cache_home().join("deno"));
+    // `deno clean` exists since Deno 2.0
+    let command = tool_output("deno", &["clean", "--help"]).map(|_| ("deno", &["clean"][..]));
+
+    clean_cache_dir("Deno cache", deno_dir, command, dry_run, yes)
+}
+
+/// Cleans `dir` with the package manager's own command when available, falling
+/// back to deleting the directory, and reports the exact change in size.
+fn clean_cache_dir(
+    name: &str,
+    dir: PathBuf,
+    command: Option<(&str, &[&str])>,
+    dry_run: bool,
+    yes: bool,
+) -> Result<u64> {
+    if !dir.exists() {
+        println!("    {} {} not found", style("ℹ").dim(), name);
+        return Ok(0);
+    }
+
+    let size_before = calculate_directory_size(&dir)?;
+    if size_before == 0 {
+        return Ok(0);
+    }
+
+    if dry_run {
+        println!("    Would clean {} ({}): {}", name, dir.display(), format_size(size_before));
+        return Ok(size_before);
+    }
+
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Clean {}? ({})", name, format_size(size_before)))
+        .default(true)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    match command {
+        Some((program, args)) => {
+            let output = Command::new(program).args(args).output()?;
+            if !output.status.success() {
+                return Err(anyhow::anyhow!("`{} {}` failed: {}",
+                    program,
+                    args.join(" "),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+        }
+        None => crate::utils::remove_path(&dir)?,
+    }
+
+    let size_after = if dir.exists() { calculate_directory_size(&dir)? } else { 0 };
+    println!("    {} {}: {} -> {}", style("✓").green(), name, format_size(size_before), format_size(size_after));
+
+    Ok(size_before.saturating_sub(size_after))
+}
+
+fn is_yarn_classic() -> bool {
+    tool_output("yarn", &["--version"]).is_some_and(|version| version.starts_with("1."))
+}
+
+/// Trimmed stdout of a successful command, or `None` when it is missing or fails.
+fn tool_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!stdout.is_empty()).then_some(stdout)
+}
+
+fn home() -> PathBuf {
+    PathBuf::from(shellexpand::tilde("~").as_ref())
+}
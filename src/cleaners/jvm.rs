@@ -0,0 +1,280 @@
+use anyhow::Result;
+use console::style;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+use super::Removal;
+use crate::utils::{calculate_directory_size, file_name, format_size, read_dirs};
+
+/// Runs the Gradle and Maven cleaners in turn.
+pub async fn cleanup_jvm(dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    println!("  {} Gradle", style("🐘").cyan());
+    total_freed += cleanup_gradle(dry_run, yes).await?;
+    println!("  {} Maven", style("🪶").cyan());
+    total_freed += cleanup_maven(dry_run, yes).await?;
+
+    Ok(total_freed)
+}
+
+/// Removes wrapper distributions, version-specific caches and daemon logs for
+/// Gradle versions that no project's `gradle-wrapper.properties` asks for. The
+/// shared dependency cache (`caches/modules-2`) is kept.
+pub async fn cleanup_gradle(dry_run: bool, yes: bool) -> Result<u64> {
+    let gradle_home = gradle_user_home();
+    if !gradle_home.exists() {
+        println!("    {} {} not found", style("ℹ").dim(), gradle_home.display());
+        return Ok(0);
+    }
+
+    let mut used = find_wrapper_versions(&crate::config::load()?.project_roots());
+    if let Some(version) = system_gradle_version() {
+        used.insert(version);
+    }
+
+    // Everything Gradle stores per version, grouped by that version
+    let mut groups: BTreeMap<String, Vec<(&str, PathBuf)>> = BTreeMap::new();
+    for dist in read_dirs(&gradle_home.join("wrapper/dists")) {
+        let name = file_name(&dist);
+        // gradle-8.5-bin or gradle-8.5-all
+        if let Some(version) = name
+            .strip_prefix("gradle-")
+            .and_then(|rest| rest.strip_suffix("-bin").or_else(|| rest.strip_suffix("-all")))
+        {
+            groups.entry(version.to_string()).or_default().push(("wrapper", dist));
+        }
+    }
+    for (kind, dir) in [("caches", gradle_home.join("caches")), ("daemon", gradle_home.join("daemon"))] {
+        for versioned in read_dirs(&dir) {
+            // Version directories sit next to shared ones such as modules-2 and jars-9
+            if file_name(&versioned).starts_with(|c: char| c.is_ascii_digit()) {
+                groups.entry(file_name(&versioned)).or_default().push((kind, versioned));
+            }
+        }
+    }
+
+    if groups.is_empty() {
+        println!("    {} No Gradle distributions found", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    let mut removals = Vec::new();
+    let mut unused_versions = Vec::new();
+    for (version, paths) in groups {
+        let sizes: Vec<(&str, PathBuf, u64)> = paths
+            .into_iter()
+            .map(|(kind, path)| {
+                let size = calculate_directory_size(&path).unwrap_or(0);
+                (kind, path, size)
+            })
+            .collect();
+        let total: u64 = sizes.iter().map(|(_, _, size)| size).sum();
+        let is_used = used.contains(&version);
+
+        println!("    Gradle {:<14} {:>12}  {}",
+            version,
+            format_size(total),
+            if is_used { style("used by a project".to_string()).green() } else { style("unused".to_string()).yellow() }
+        );
+
+        if !is_used {
+            unused_versions.push(version.clone());
+            removals.extend(sizes.into_iter().map(|(kind, path, size)| Removal {
+                description: format!("Gradle {} {}", version, kind),
+                path,
+                size,
+            }));
+        }
+    }
+
+    println!();
+    // A running daemon keeps its distribution and caches open, so stop them first
+    super::remove_with_confirmation_after("unused Gradle versions", &removals, dry_run, yes, || {
+        stop_gradle_daemons(&gradle_home, &unused_versions)
+    })
+}
+
+/// Removes `-SNAPSHOT` versions and releases superseded by a newer version of the
+/// same artifact from the local Maven repository.
+pub async fn cleanup_maven(dry_run: bool, yes: bool) -> Result<u64> {
+    let repository = maven_repository();
+    if !repository.exists() {
+        println!("    {} {} not found", style("ℹ").dim(), repository.display());
+        return Ok(0);
+    }
+
+    let mut removals = Vec::new();
+    let mut group_sizes: HashMap<String, u64> = HashMap::new();
+
+    for artifact in find_maven_artifacts(&repository) {
+        // Subdirectories without a .pom are nested artifacts, not versions
+        let versions: Vec<PathBuf> = read_dirs(&artifact).into_iter().filter(|dir| has_pom(dir)).collect();
+        let newest_release = versions
+            .iter()
+            .map(|dir| file_name(dir))
+            .filter(|version| !version.ends_with("-SNAPSHOT"))
+            .max_by(|a, b| compare_maven_versions(a, b));
+
+        for dir in versions {
+            let version = file_name(&dir);
+            let reason = if version.ends_with("-SNAPSHOT") {
+                "snapshot"
+            } else if newest_release
+                .as_ref()
+                .is_some_and(|newest| compare_maven_versions(&version, newest) == Ordering::Less)
+            {
+                "superseded"
+            } else {
+                continue;
+            };
+
+            // groupId/artifactId as a Maven coordinate, e.g. org.slf4j:slf4j-api
+            let relative = artifact.strip_prefix(&repository).unwrap_or(&artifact);
+            let group = relative.parent().map(|p| p.to_string_lossy().replace('/', ".")).unwrap_or_default();
+            let size = calculate_directory_size(&dir).unwrap_or(0);
+            *group_sizes.entry(group.clone()).or_default() += size;
+
+            removals.push(Removal {
+                description: format!("{}:{}:{} ({})", group, file_name(&artifact), version, reason),
+                path: dir,
+                size,
+            });
+        }
+    }
+
+    if removals.is_empty() {
+        println!("    {} No snapshots or superseded versions found", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    let mut groups: Vec<(String, u64)> = group_sizes.into_iter().collect();
+    groups.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    for (group, size) in groups.iter().take(10) {
+        println!("    {:<50} {:>12}", group, format_size(*size));
+    }
+    if groups.len() > 10 {
+        println!("    ... and {} more groups", groups.len() - 10);
+    }
+
+    println!();
+    super::remove_with_confirmation("old Maven artifacts", &removals, dry_run, yes)
+}
+
+fn gradle_user_home() -> PathBuf {
+    match env::var("GRADLE_USER_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(shellexpand::tilde("~/.gradle").as_ref()),
+    }
+}
+
+fn maven_repository() -> PathBuf {
+    PathBuf::from(shellexpand::tilde("~/.m2/repository").as_ref())
+}
+
+/// Gradle versions requested by `gradle/wrapper/gradle-wrapper.properties` files
+/// under the project roots.
+fn find_wrapper_versions(roots: &[PathBuf]) -> HashSet<String> {
+    let mut versions = HashSet::new();
+
+    for root in roots {
+        for entry in crate::utils::walk_project_root(root) {
+            if entry.file_name() != "gradle-wrapper.properties" {
+                continue;
+            }
+            let Ok(contents) = fs::read_to_string(entry.path()) else { continue };
+            // distributionUrl=https\://services.gradle.org/distributions/gradle-8.5-bin.zip
+            let version = contents.lines().find_map(|line| {
+                let url = line.trim().strip_prefix("distributionUrl")?.trim_start().strip_prefix('=')?;
+                let archive = url.rsplit('/').next()?;
+                let version = archive.strip_prefix("gradle-")?;
+                version.strip_suffix("-bin.zip").or_else(|| version.strip_suffix("-all.zip")).map(String::from)
+            });
+            if let Some(version) = version {
+                versions.insert(version);
+            }
+        }
+    }
+
+    versions
+}
+
+/// The version of a `gradle` installed outside the wrapper, e.g. by a package manager.
+fn system_gradle_version() -> Option<String> {
+    let output = Command::new("gradle").arg("--version").output().ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Gradle ").map(|version| version.trim().to_string()))
+}
+
+/// Asks the daemons of each version to stop using that version's own launcher,
+/// since a launcher only stops daemons of its own version.
+fn stop_gradle_daemons(gradle_home: &Path, versions: &[String]) {
+    for version in versions {
+        let launchers = WalkDir::new(gradle_home.join("wrapper/dists"))
+            .max_depth(5)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .map(|entry| entry.into_path())
+            .filter(|path| path.ends_with(format!("gradle-{}/bin/gradle", version)));
+
+        for launcher in launchers {
+            if let Ok(output) = Command::new(&launcher).arg("--stop").output() {
+                if output.status.success() {
+                    println!("    {} Stopped Gradle {} daemons", style("✓").green(), version);
+                }
+            }
+        }
+    }
+}
+
+/// Artifact directories (`<group path>/<artifactId>`) of the repository: the
+/// parents of version directories that contain a `.pom`.
+fn find_maven_artifacts(repository: &Path) -> BTreeSet<PathBuf> {
+    WalkDir::new(repository)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "pom"))
+        .filter_map(|entry| entry.path().parent().and_then(Path::parent).map(Path::to_path_buf))
+        .collect()
+}
+
+fn has_pom(dir: &Path) -> bool {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .any(|entry| entry.path().extension().is_some_and(|ext| ext == "pom"))
+}
+
+/// Orders versions by their numeric components, ranking a plain release above a
+/// qualified one (`1.2.0` > `1.2.0-RC1`). Versions that only differ in their
+/// qualifier compare equal, so neither is considered superseded.
+fn compare_maven_versions(a: &str, b: &str) -> Ordering {
+    fn key(version: &str) -> (Vec<u64>, bool) {
+        let mut numbers = Vec::new();
+        let mut qualified = false;
+        for part in version.split(['.', '-']) {
+            match part.parse::<u64>() {
+                Ok(number) if !qualified => numbers.push(number),
+                _ => {
+                    let lower = part.to_ascii_lowercase();
+                    if !matches!(lower.as_str(), "final" | "release" | "ga") {
+                        qualified = true;
+                    }
+                }
+            }
+        }
+        while numbers.last() == Some(&0) {
+            numbers.pop();
+        }
+        (numbers, !qualified)
+    }
+
+    key(a).cmp(&key(b))
+}
@@ -0,0 +1,221 @@
+use anyhow::Result;
+use console::style;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+use walkdir::WalkDir;
+
+use super::Removal;
+use crate::utils::{days_ago, format_size, state_home};
+
+/// Rotated logs and archived journal files older than this are removed by default.
+const DEFAULT_MAX_AGE_DAYS: u64 = 30;
+
+/// The journal is vacuumed down to this size.
+const JOURNAL_MAX_SIZE: u64 = 500 * 1024 * 1024;
+
+/// Where systemd-journald keeps persistent and volatile journals.
+const JOURNAL_DIRS: &[&str] = &["/var/log/journal", "/run/log/journal"];
+
+/// Runs the system log cleaner (when allowed) and the user log cleaner.
+pub async fn cleanup_logs(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    println!("  {} System logs", style("🖥️").cyan());
+    total_freed += cleanup_system_logs(min_age_days, dry_run, yes).await?;
+    println!("  {} User logs", style("👤").cyan());
+    total_freed += cleanup_user_logs(min_age_days, dry_run, yes).await?;
+
+    Ok(total_freed)
+}
+
+/// Vacuums the systemd journal and removes rotated logs from `/var/log`.
+pub async fn cleanup_system_logs(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    if !super::system::system_cleaners_allowed() {
+        return Ok(0);
+    }
+
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_AGE_DAYS);
+    let mut total_freed = cleanup_journal(days, dry_run, yes)?;
+
+    let removals = find_rotated_logs(Path::new("/var/log"), days_ago(days), false);
+    println!("    Rotated logs in /var/log older than {} days: {}",
+        days, format_size(removals.iter().map(|r| r.size).sum()));
+    total_freed += super::remove_with_confirmation("rotated system logs", &removals, dry_run, yes)?;
+
+    Ok(total_freed)
+}
+
+/// Removes rotated logs from the user's state directory (and `~/Library/Logs` on macOS).
+pub async fn cleanup_user_logs(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_AGE_DAYS);
+    let cutoff = days_ago(days);
+
+    let mut dirs = vec![state_home()];
+    if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from(shellexpand::tilde("~/Library/Logs").as_ref()));
+    }
+
+    // Applications keep other compressed state here too, so only touch files that look like logs
+    let removals: Vec<Removal> = dirs.iter().flat_map(|dir| find_rotated_logs(dir, cutoff, true)).collect();
+    println!("    Rotated logs older than {} days: {}", days, format_size(removals.iter().map(|r| r.size).sum()));
+    super::remove_with_confirmation("rotated user logs", &removals, dry_run, yes)
+}
+
+/// Reports journal disk usage and runs `journalctl --vacuum-size/--vacuum-time`.
+/// journalctl has no dry run, so the estimate replays its rules on the archived
+/// files: oldest first, drop those past the age limit or over the size limit.
+fn cleanup_journal(days: u64, dry_run: bool, yes: bool) -> Result<u64> {
+    let files = journal_files();
+    if files.is_empty() {
+        println!("    {} No systemd journal found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let usage_before: u64 = files.iter().map(|(_, _, size, _)| size).sum();
+    println!("    Journal disk usage: {} (limit {}, {} days)",
+        format_size(usage_before), format_size(JOURNAL_MAX_SIZE), days);
+
+    let cutoff = days_ago(days);
+    let mut remaining = usage_before;
+    let mut estimate = 0u64;
+    for (_, modified, size, archived) in &files {
+        if *archived && (*modified < cutoff || remaining > JOURNAL_MAX_SIZE) {
+            remaining -= size;
+            estimate += size;
+        }
+    }
+
+    if estimate == 0 {
+        println!("    {} Journal is within limits", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    if dry_run {
+        println!("  Would vacuum the journal: {}", format_size(estimate));
+        return Ok(estimate);
+    }
+
+    if !yes && !dialoguer::Confirm::new()
+        .with_prompt(format!("Vacuum the journal? (about {})", format_size(estimate)))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    let output = Command::new("journalctl")
+        .arg(format!("--vacuum-size={}", JOURNAL_MAX_SIZE))
+        .arg(format!("--vacuum-time={}d", days))
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("journalctl --vacuum failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    let usage_after: u64 = journal_files().iter().map(|(_, _, size, _)| size).sum();
+    Ok(usage_before.saturating_sub(usage_after))
+}
+
+/// Journal files as `(path, modified, size, archived)`, oldest first. Only
+/// archived files (`name@...journal` or `.journal~`) are ever vacuumed.
+fn journal_files() -> Vec<(PathBuf, SystemTime, u64, bool)> {
+    let mut files: Vec<(PathBuf, SystemTime, u64, bool)> = JOURNAL_DIRS
+        .iter()
+        .flat_map(|dir| WalkDir::new(dir).max_depth(2).into_iter().filter_map(|e| e.ok()))
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if !name.ends_with(".journal") && !name.ends_with(".journal~") {
+                return None;
+            }
+            let metadata = entry.metadata().ok()?;
+            let archived = name.contains('@') || name.ends_with('~');
+            Some((entry.into_path(), metadata.modified().ok()?, metadata.len(), archived))
+        })
+        .collect();
+    files.sort_by_key(|(_, modified, _, _)| *modified);
+    files
+}
+
+/// Rotated logs under `dir` last modified before `cutoff`: compressed logs,
+/// numbered rotations (`syslog.1`) and dated ones (`messages-20240101`). With
+/// `log_paths_only`, the path below `dir` must also look like a log path.
+fn find_rotated_logs(dir: &Path, cutoff: SystemTime, log_paths_only: bool) -> Vec<Removal> {
+    WalkDir::new(dir)
+        .max_depth(4)
+        .into_iter()
+        // The journal is handled by journalctl
+        .filter_entry(|entry| entry.file_name() != "journal")
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file() && is_rotated_log(&entry.file_name().to_string_lossy()))
+        .filter(|entry| {
+            !log_paths_only
+                || entry.path().strip_prefix(dir).is_ok_and(is_log_path)
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            (metadata.modified().ok()? < cutoff).then(|| Removal {
+                description: entry.path().display().to_string(),
+                size: metadata.len(),
+                path: entry.into_path(),
+            })
+        })
+        .collect()
+}
+
+/// Whether a component of `relative` is a `log` or `logs` directory or a `.log`
+/// file, rotated (`app.log.1`) or not. Whole components only, so `catalog` or
+/// `changelog` do not count.
+fn is_log_path(relative: &Path) -> bool {
+    relative.components().any(|component| {
+        let name = component.as_os_str().to_string_lossy().to_ascii_lowercase();
+        name == "log" || name == "logs" || name.ends_with(".log") || name.contains(".log.")
+    })
+}
+
+fn is_rotated_log(name: &str) -> bool {
+    let is_numbered = name
+        .rsplit_once('.')
+        .is_some_and(|(stem, suffix)| !stem.is_empty() && !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit()));
+    let is_dated = name
+        .rsplit_once('-')
+        .is_some_and(|(_, suffix)| suffix.len() == 8 && suffix.chars().all(|c| c.is_ascii_digit()));
+
+    is_numbered
+        || is_dated
+        || [".gz", ".xz", ".bz2", ".zst", ".old"].iter().any(|ext| name.ends_with(ext))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_rotated_logs() {
+        assert!(is_rotated_log("syslog.1"));
+        assert!(is_rotated_log("app.log.2.gz"));
+        assert!(is_rotated_log("messages-20240101"));
+        assert!(is_rotated_log("Xorg.0.log.old"));
+        assert!(is_rotated_log("kern.log.xz"));
+    }
+
+    #[test]
+    fn ignores_live_logs() {
+        assert!(!is_rotated_log("syslog"));
+        assert!(!is_rotated_log("app.log"));
+        assert!(!is_rotated_log(".1"));
+        assert!(!is_rotated_log("build-2024"));
+        assert!(!is_rotated_log("notes.txt"));
+    }
+
+    #[test]
+    fn log_paths_match_whole_components() {
+        assert!(is_log_path(Path::new("myapp/logs/app.1")));
+        assert!(is_log_path(Path::new("myapp/log/app.1")));
+        assert!(is_log_path(Path::new("myapp/server.log.1")));
+        assert!(is_log_path(Path::new("myapp/server.log")));
+        assert!(!is_log_path(Path::new("catalog/index.1")));
+        assert!(!is_log_path(Path::new("blog/post.gz")));
+        assert!(!is_log_path(Path::new("docs/changelog.1")));
+    }
+}
@@ -1,91 +1,285 @@
+pub mod browsers;
 pub mod caches;
+pub mod cargo;
+pub mod crashes;
+pub mod desktop;
 pub mod docker;
+pub mod downloads;
+pub mod flatpak;
+pub mod git;
+pub mod go;
+pub mod infra;
+pub mod ide;
+pub mod javascript;
+pub mod jvm;
+pub mod logs;
+pub mod nix;
+pub mod node;
+pub mod profiles;
+pub mod python;
+pub mod rustup;
+pub mod snap;
+pub mod system;
+pub mod temp;
+pub mod vm;
 
 use anyhow::Result;
 use console::style;
+use dialoguer::Confirm;
+use std::path::PathBuf;
 
-pub async fn run_quick_cleanup(dry_run: bool, yes: bool) -> Result<()> {
-    println!("{}", style("🚀 Running Quick Cleanup...").bold().green());
-    println!("This will clean safe cache directories that can be easily regenerated.\n");
-    
-    let mut total_freed = 0u64;
-    
-    // Homebrew Cache
-    println!("{} {}...", style("🧹").cyan(), "Homebrew Cache");
-    match caches::cleanup_homebrew(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
+use profiles::Profile;
+
+/// Cleanup targets that profiles can refer to, with their display names.
+pub const TARGETS: &[(&str, &str)] = &[
+    ("homebrew", "Homebrew Cache"),
+    ("pip", "pip Cache"),
+    ("npm", "npm Cache"),
+    ("composer", "Composer Cache"),
+    ("node-gyp", "node-gyp Cache"),
+    ("playwright", "Playwright Cache"),
+    ("browsers", "Browser Caches"),
+    ("temp", "System Temp Files"),
+    ("docker", "Docker"),
+    ("cargo", "Cargo Registry and Build Artifacts"),
+    ("rustup", "Unused Rust Toolchains"),
+    ("node-versions", "Unused Node.js Versions"),
+    ("python", "Python Environments and Caches"),
+    ("pyenv", "Unused pyenv Versions"),
+    ("conda", "conda Packages and Environments"),
+    ("poetry", "Poetry Cache and Virtualenvs"),
+    ("uv", "uv Cache"),
+    ("pipx", "pipx Shared Libraries"),
+    ("pycache", "Python Bytecode and Tool Caches"),
+    ("js-caches", "JavaScript Package Manager Caches"),
+    ("yarn", "Yarn Cache"),
+    ("pnpm", "pnpm Store"),
+    ("bun", "Bun Install Cache"),
+    ("deno", "Deno Cache"),
+    ("jvm", "Gradle and Maven Caches"),
+    ("gradle", "Unused Gradle Versions"),
+    ("maven", "Old Maven Artifacts"),
+    ("go", "Go Module and Build Caches"),
+    ("packages", "System Package Caches"),
+    ("apt", "apt Package Cache"),
+    ("dnf", "dnf/yum Package Cache"),
+    ("pacman", "Old pacman Packages"),
+    ("zypper", "zypper Package Cache"),
+    ("logs", "Journal and Rotated Logs"),
+    ("system-logs", "Journal and Rotated System Logs"),
+    ("user-logs", "Rotated User Logs"),
+    ("snap", "Disabled Snap Revisions"),
+    ("flatpak", "Unused Flatpak Runtimes"),
+    ("desktop", "Trash, Thumbnails and Recent Files"),
+    ("trash", "Old Trash Items"),
+    ("thumbnails", "Orphaned Thumbnails"),
+    ("recent-files", "Stale Recent Files Entries"),
+    ("ide", "Editor and IDE Caches"),
+    ("vscode", "VS Code Caches and Old Extensions"),
+    ("jetbrains", "Old JetBrains IDE Data"),
+    ("git", "Git Repository Maintenance"),
+    ("vm", "Virtual Machine and Emulator Images"),
+    ("vagrant", "Unused Vagrant Boxes"),
+    ("libvirt", "Unused libvirt Images"),
+    ("virtualbox", "Unregistered VirtualBox VMs"),
+    ("android-emulator", "Unused Android System Images"),
+    ("minikube", "Unused minikube Caches"),
+    ("kind", "Unused kind Node Images"),
+    ("infra", "Infrastructure Tooling Caches"),
+    ("terraform", "Terraform Provider Cache and .terraform Dirs"),
+    ("helm", "Helm Repository Cache"),
+    ("kubectl", "kubectl Discovery and HTTP Cache"),
+    ("bazel", "Bazel Output Bases of Removed Workspaces"),
+    ("ccache", "ccache (trimmed to size limit)"),
+    ("sccache", "sccache (trimmed to size limit)"),
+    ("nix", "Old Nix Generations and Store Garbage"),
+    ("crashes", "Core Dumps and Crash Reports"),
+    ("downloads", "Old Downloads (moved to Trash)"),
+];
+
+pub fn target_label(target: &str) -> &str {
+    TARGETS
+        .iter()
+        .find(|(name, _)| *name == target)
+        .map(|(_, label)| *label)
+        .unwrap_or(target)
+}
+
+/// Runs a single profile target and returns the bytes it freed.
+pub async fn run_target(target: &str, profile: &Profile, dry_run: bool, yes: bool) -> Result<u64> {
+    let min_age_days = profile.retention.min_age_days;
+    let max_cache_size = profile.retention.max_cache_size_mb.map(|mb| mb * 1024 * 1024);
+
+    match target {
+        "homebrew" | "pip" | "composer" | "node-gyp" | "playwright" => {
+            caches::cleanup_cache_target(target, min_age_days, dry_run, yes).await
         }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
+        "npm" => caches::cleanup_npm(dry_run, yes).await,
+        "browsers" => browsers::cleanup_browser_caches(dry_run, yes).await,
+        "temp" => temp::cleanup_temp_files(min_age_days, dry_run, yes).await,
+        "docker" => docker::cleanup_docker_with_options(&profile.docker, dry_run, yes).await,
+        "cargo" => cargo::cleanup_cargo(min_age_days, dry_run, yes).await,
+        "rustup" => rustup::cleanup_rustup_toolchains(dry_run, yes).await,
+        "node-versions" => node::cleanup_node_versions(dry_run, yes).await,
+        "python" => python::cleanup_python(min_age_days, dry_run, yes).await,
+        "pyenv" => python::cleanup_pyenv_versions(dry_run, yes).await,
+        "conda" => python::cleanup_conda(min_age_days, dry_run, yes).await,
+        "poetry" => python::cleanup_poetry(min_age_days, dry_run, yes).await,
+        "uv" => python::cleanup_uv(dry_run, yes).await,
+        "pipx" => python::cleanup_pipx(dry_run, yes).await,
+        "pycache" => python::cleanup_project_caches(dry_run, yes).await,
+        "js-caches" => javascript::cleanup_js_caches(dry_run, yes).await,
+        "yarn" => javascript::cleanup_yarn(dry_run, yes).await,
+        "pnpm" => javascript::cleanup_pnpm(dry_run, yes).await,
+        "bun" => javascript::cleanup_bun(dry_run, yes).await,
+        "deno" => javascript::cleanup_deno(dry_run, yes).await,
+        "jvm" => jvm::cleanup_jvm(dry_run, yes).await,
+        "gradle" => jvm::cleanup_gradle(dry_run, yes).await,
+        "maven" => jvm::cleanup_maven(dry_run, yes).await,
+        "go" => go::cleanup_go(min_age_days, dry_run, yes).await,
+        "packages" => system::cleanup_package_caches(dry_run, yes).await,
+        "apt" => system::cleanup_apt(dry_run, yes).await,
+        "dnf" => system::cleanup_dnf(dry_run, yes).await,
+        "pacman" => system::cleanup_pacman(dry_run, yes).await,
+        "zypper" => system::cleanup_zypper(dry_run, yes).await,
+        "logs" => logs::cleanup_logs(min_age_days, dry_run, yes).await,
+        "system-logs" => logs::cleanup_system_logs(min_age_days, dry_run, yes).await,
+        "user-logs" => logs::cleanup_user_logs(min_age_days, dry_run, yes).await,
+        "snap" => snap::cleanup_snap_revisions(dry_run, yes).await,
+        "flatpak" => flatpak::cleanup_flatpak_runtimes(dry_run, yes).await,
+        "desktop" => desktop::cleanup_desktop(min_age_days, dry_run, yes).await,
+        "trash" => desktop::cleanup_trash(min_age_days, dry_run, yes).await,
+        "thumbnails" => desktop::cleanup_thumbnails(dry_run, yes).await,
+        "recent-files" => desktop::cleanup_recent_files(min_age_days, dry_run, yes).await,
+        "ide" => ide::cleanup_ide_caches(dry_run, yes).await,
+        "vscode" => ide::cleanup_vscode(dry_run, yes).await,
+        "jetbrains" => ide::cleanup_jetbrains(dry_run, yes).await,
+        "git" => git::cleanup_git_repos(dry_run, yes).await,
+        "vm" => vm::cleanup_vm_images(dry_run, yes).await,
+        "vagrant" => vm::cleanup_vagrant(dry_run, yes).await,
+        "libvirt" => vm::cleanup_libvirt(dry_run, yes).await,
+        "virtualbox" => vm::cleanup_virtualbox(dry_run, yes).await,
+        "android-emulator" => vm::cleanup_android_emulator(dry_run, yes).await,
+        "minikube" => vm::cleanup_minikube(dry_run, yes).await,
+        "kind" => vm::cleanup_kind(dry_run, yes).await,
+        "infra" => infra::cleanup_infra_caches(min_age_days, max_cache_size, dry_run, yes).await,
+        "terraform" => infra::cleanup_terraform(min_age_days, dry_run, yes).await,
+        "helm" => infra::cleanup_helm(min_age_days, dry_run, yes).await,
+        "kubectl" => infra::cleanup_kubectl(min_age_days, dry_run, yes).await,
+        "bazel" => infra::cleanup_bazel(dry_run, yes).await,
+        "ccache" => infra::cleanup_ccache(max_cache_size, dry_run, yes).await,
+        "sccache" => infra::cleanup_sccache(max_cache_size, dry_run, yes).await,
+        "nix" => nix::cleanup_nix(min_age_days, dry_run, yes).await,
+        "crashes" => crashes::cleanup_crash_dumps(min_age_days, dry_run, yes).await,
+        "downloads" => downloads::cleanup_downloads(min_age_days, dry_run, yes).await,
+        _ => Err(anyhow::anyhow!(
+            "Unknown target '{}' (expected one of: {})",
+            target,
+            TARGETS.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")
+        )),
     }
+}
+
+/// A file or directory a cleaner proposes to delete.
+pub struct Removal {
+    pub path: PathBuf,
+    pub size: u64,
+    pub description: String,
+}
+
+/// Lists `removals`, asks once for all of them and deletes them. Returns the bytes
+/// freed, or the bytes that would be freed in dry-run mode.
+pub fn remove_with_confirmation(name: &str, removals: &[Removal], dry_run: bool, yes: bool) -> Result<u64> {
+    confirm_and_remove(name, removals, dry_run, yes, || {}, |removal| crate::utils::remove_path(&removal.path))
+}
+
+/// Like [`remove_with_confirmation`], but runs `prepare` once the removal is
+/// confirmed, e.g. to stop a daemon holding the files open. It never runs in
+/// dry-run mode or when the removal is declined.
+pub fn remove_with_confirmation_after(
+    name: &str,
+    removals: &[Removal],
+    dry_run: bool,
+    yes: bool,
+    prepare: impl FnOnce(),
+) -> Result<u64> {
+    confirm_and_remove(name, removals, dry_run, yes, prepare, |removal| crate::utils::remove_path(&removal.path))
+}
+
+/// Like [`remove_with_confirmation`], but deletes each entry with `remove`
+/// instead of removing whole trees.
+pub fn remove_with_confirmation_using(
+    name: &str,
+    removals: &[Removal],
+    dry_run: bool,
+    yes: bool,
+    remove: impl FnMut(&Removal) -> Result<()>,
+) -> Result<u64> {
+    confirm_and_remove(name, removals, dry_run, yes, || {}, remove)
+}
+
+fn confirm_and_remove(
+    name: &str,
+    removals: &[Removal],
+    dry_run: bool,
+    yes: bool,
+    prepare: impl FnOnce(),
+    mut remove: impl FnMut(&Removal) -> Result<()>,
+) -> Result<u64> {
+    let total: u64 = removals.iter().map(|removal| removal.size).sum();
     
-    // pip Cache
-    println!("{} {}...", style("🧹").cyan(), "pip Cache");
-    match caches::cleanup_pip(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
+    if removals.is_empty() {
+        return Ok(0);
     }
     
-    // npm Cache
-    println!("{} {}...", style("🧹").cyan(), "npm Cache");
-    match caches::cleanup_npm(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
+    let mut largest: Vec<&Removal> = removals.iter().collect();
+    largest.sort_by_key(|removal| std::cmp::Reverse(removal.size));
+    for removal in largest.iter().take(10) {
+        println!("    {} {}",
+            style(&removal.description).dim(),
+            crate::utils::format_size(removal.size)
+        );
+    }
+    if largest.len() > 10 {
+        println!("    {}", style(format!("... and {} more", largest.len() - 10)).dim());
     }
     
-    // Composer Cache
-    println!("{} {}...", style("🧹").cyan(), "Composer Cache");
-    match caches::cleanup_composer(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
+    if dry_run {
+        println!("  Would clean {}: {}", name, crate::utils::format_size(total));
+        return Ok(total);
     }
     
-    // node-gyp Cache
-    println!("{} {}...", style("🧹").cyan(), "node-gyp Cache");
-    match caches::cleanup_node_gyp(dry_run, yes).await {
-        Ok(size) => {
-            if size > 0 {
-                println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
-                total_freed += size;
-            } else {
-                println!("  {} Nothing to clean", style("ℹ").blue());
-            }
-        }
-        Err(e) => println!("  {} Error: {}", style("✗").red(), e),
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Clean {} ({} items, {})?", name, removals.len(), crate::utils::format_size(total)))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
     }
     
-    if total_freed > 0 {
-        println!("\n{} Total freed: {}", 
-            style("🎉").green(),
-            crate::utils::format_size(total_freed)
-        );
+    prepare();
+    
+    let mut freed = 0u64;
+    for removal in removals {
+        match remove(removal) {
+            Ok(()) => freed += removal.size,
+            Err(e) => println!("    {} Could not remove {}: {}",
+                style("✗").red(),
+                removal.path.display(),
+                e
+            ),
+        }
     }
     
+    Ok(freed)
+}
+
+pub async fn run_quick_cleanup(dry_run: bool, yes: bool) -> Result<()> {
+    println!("{}", style("🚀 Running Quick Cleanup...").bold().green());
+    println!("This will clean safe cache directories that can be easily regenerated.\n");
+
+    let profile = profiles::find_profile("quick")?;
+    profiles::run_profile("quick", &profile, dry_run, yes).await?;
+
     Ok(())
-}
\ No newline at end of file
+}
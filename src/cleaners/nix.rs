@@ -0,0 +1,384 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::Confirm;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use crate::utils::{days_ago, format_date, format_size, state_home};
+
+/// Generations and `result` links older than this are removed by default.
+const DEFAULT_MAX_AGE_DAYS: u64 = 30;
+
+/// Store paths passed to a single `nix-store` invocation.
+const STORE_PATH_CHUNK: usize = 500;
+
+const NIX_STORE: &str = "/nix/store";
+
+const NIX_DB: &str = "/nix/var/nix/db/db.sqlite";
+
+/// A profile whose generations can be deleted: `nix-env -p <path>`.
+struct NixProfile {
+    name: &'static str,
+    path: PathBuf,
+    /// System profiles belong to root and need `--system`
+    system: bool,
+}
+
+struct Generation {
+    number: u64,
+    link: PathBuf,
+    created: SystemTime,
+}
+
+/// A GC root, the symlink that keeps it alive and the store path it points to.
+struct GcRoot {
+    link: PathBuf,
+    target: PathBuf,
+}
+
+/// Reports the store size, old generations of the user, home-manager and system
+/// profiles and `result` links in project directories, then deletes generations
+/// and links older than `min_age_days` and runs the garbage collector.
+pub async fn cleanup_nix(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    if !Path::new(NIX_STORE).is_dir() || Command::new("nix-store").arg("--version").output().is_err() {
+        println!("  {} Nix not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_AGE_DAYS);
+    let cutoff = days_ago(days);
+    match store_size() {
+        Some(size) => println!("  {} Nix store: {}", style("❄️").cyan(), format_size(size)),
+        None => println!("  {} Nix store: size unknown", style("❄️").cyan()),
+    }
+
+    let mut old_generations: Vec<(&NixProfile, Vec<Generation>)> = Vec::new();
+    let profiles = nix_profiles();
+    let mut system_allowed = None;
+    for profile in &profiles {
+        let generations = generations(&profile.path);
+        let current = current_generation(&profile.path);
+        let old: Vec<Generation> = generations
+            .into_iter()
+            .filter(|generation| Some(generation.number) != current && generation.created < cutoff)
+            .collect();
+
+        println!("    {:<14} generation {}, {} older than {} days",
+            profile.name,
+            current.map(|number| number.to_string()).unwrap_or_else(|| "?".to_string()),
+            old.len(),
+            days
+        );
+        if old.is_empty() {
+            continue;
+        }
+        // Check whether system cleaners may run at most once, and only when a
+        // system profile has generations to delete, so the reason is printed once
+        if profile.system && !*system_allowed.get_or_insert_with(super::system::system_cleaners_allowed) {
+            continue;
+        }
+        old_generations.push((profile, old));
+    }
+
+    let roots = gc_roots();
+    let project_roots = crate::config::load()?.project_roots();
+    let mut stale_links = Vec::new();
+    let mut project_links: Vec<(&GcRoot, u64, bool)> = Vec::new();
+    for root in &roots {
+        let in_project = project_roots.iter().any(|project| root.link.starts_with(project));
+        if !in_project {
+            continue;
+        }
+        let created = fs::symlink_metadata(&root.link).and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+        // Only build results are ours to delete; direnv and devenv manage their own roots
+        let stale = is_result_link(&root.link) && created < cutoff;
+        project_links.push((root, closure_size(std::slice::from_ref(&root.target)), stale));
+        if stale {
+            stale_links.push(root.link.clone());
+        }
+    }
+    if !project_links.is_empty() {
+        project_links.sort_by_key(|(_, size, _)| std::cmp::Reverse(*size));
+        println!("    GC roots in project directories:");
+        for (root, size, stale) in project_links.iter().take(10) {
+            let created = fs::symlink_metadata(&root.link).and_then(|m| m.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+            println!("      {:<50} {:>12}  {}{}",
+                root.link.display(),
+                format_size(*size),
+                format_date(created),
+                if *stale { style(" (stale)").yellow().to_string() } else { String::new() }
+            );
+        }
+    }
+
+    let generation_count: usize = old_generations.iter().map(|(_, generations)| generations.len()).sum();
+    let removed_links: HashSet<PathBuf> = old_generations
+        .iter()
+        .flat_map(|(_, generations)| generations.iter().map(|generation| generation.link.clone()))
+        .chain(stale_links.iter().cloned())
+        .collect();
+    let estimate = gc_estimate(&roots, &removed_links);
+
+    if dry_run {
+        println!("  Would delete {} generations and {} result links and collect garbage: about {}",
+            generation_count, stale_links.len(), format_size(estimate));
+        return Ok(estimate);
+    }
+
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Delete {} old generations and {} stale result links, then collect garbage (about {})?",
+            generation_count, stale_links.len(), format_size(estimate)))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    for (profile, generations) in &old_generations {
+        let output = Command::new("nix-env")
+            .arg("-p")
+            .arg(&profile.path)
+            .arg("--delete-generations")
+            .args(generations.iter().map(|generation| generation.number.to_string()))
+            .output()?;
+        if !output.status.success() {
+            println!("  {} Could not delete {} generations: {}",
+                style("✗").red(),
+                profile.name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+
+    for link in &stale_links {
+        if let Err(e) = fs::remove_file(link) {
+            println!("  {} Could not remove {}: {}", style("✗").red(), link.display(), e);
+        }
+    }
+
+    let output = Command::new("nix-store").arg("--gc").output()?;
+    if !output.status.success() {
+        println!("  {} nix-store --gc failed: {}", style("✗").red(), String::from_utf8_lossy(&output.stderr).trim());
+        return Ok(0);
+    }
+
+    // The summary ("1234 store paths deleted, 5.67 GiB freed") goes to stderr
+    let summary = String::from_utf8_lossy(&output.stderr).into_owned() + &String::from_utf8_lossy(&output.stdout);
+    Ok(summary.lines().find_map(parse_freed).unwrap_or(0))
+}
+
+/// Size of the store as the sum of the NAR sizes in the Nix database, from
+/// `nix path-info` or, without the nix command, the database itself.
+fn store_size() -> Option<u64> {
+    let output = Command::new("nix")
+        .args(["--extra-experimental-features", "nix-command", "path-info", "--all", "--size"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success());
+    if let Some(output) = output {
+        // One `<path>  <size>` line per valid path
+        return Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.split_whitespace().last()?.parse::<u64>().ok())
+                .sum(),
+        );
+    }
+
+    let output = Command::new("sqlite3")
+        .args(["-readonly", NIX_DB, "SELECT SUM(narSize) FROM ValidPaths"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// `result` and `result-<output>` links left by `nix build` and `nix-build`.
+fn is_result_link(link: &Path) -> bool {
+    link.file_name()
+        .map(|name| name.to_string_lossy())
+        .is_some_and(|name| name == "result" || name.starts_with("result-"))
+}
+
+/// The user, home-manager and system profiles that exist on this machine.
+fn nix_profiles() -> Vec<NixProfile> {
+    let user = env::var("USER").unwrap_or_default();
+    let state_home = state_home();
+    let per_user = PathBuf::from("/nix/var/nix/profiles/per-user").join(&user);
+
+    // Newer Nix keeps user profiles under XDG_STATE_HOME, older ones per-user
+    let candidates = [
+        ("user", vec![state_home.join("nix/profiles/profile"), per_user.join("profile")], false),
+        ("home-manager", vec![state_home.join("nix/profiles/home-manager"), per_user.join("home-manager")], false),
+        ("system", vec![PathBuf::from("/nix/var/nix/profiles/system")], true),
+        ("default", vec![PathBuf::from("/nix/var/nix/profiles/default")], true),
+    ];
+
+    candidates
+        .into_iter()
+        .filter_map(|(name, paths, system)| {
+            let path = paths.into_iter().find(|path| fs::symlink_metadata(path).is_ok())?;
+            Some(NixProfile { name, path, system })
+        })
+        .collect()
+}
+
+/// The `<profile>-<number>-link` symlinks next to a profile.
+fn generations(profile: &Path) -> Vec<Generation> {
+    let Some(name) = profile.file_name().map(|name| name.to_string_lossy().into_owned()) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}-", name);
+
+    let mut generations: Vec<Generation> = fs::read_dir(profile.parent().unwrap_or(Path::new("/")))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name().to_string_lossy().into_owned();
+            let number = file_name.strip_prefix(&prefix)?.strip_suffix("-link")?.parse().ok()?;
+            let created = fs::symlink_metadata(entry.path()).and_then(|m| m.modified()).ok()?;
+            Some(Generation { number, link: entry.path(), created })
+        })
+        .collect();
+    generations.sort_by_key(|generation| generation.number);
+    generations
+}
+
+/// The generation a profile points to, from its `<profile>-<number>-link` target.
+fn current_generation(profile: &Path) -> Option<u64> {
+    let target = fs::read_link(profile).ok()?;
+    let name = target.file_name()?.to_string_lossy().into_owned();
+    name.rsplit('-').nth(1)?.parse().ok()
+}
+
+/// Every GC root known to the collector, from `nix-store --gc --print-roots`.
+/// Roots held by running processes have a censored link but are still listed.
+fn gc_roots() -> Vec<GcRoot> {
+    store_query(&["--gc", "--print-roots"])
+        .iter()
+        .filter_map(|line| {
+            let (link, target) = line.split_once(" -> ")?;
+            Some(GcRoot { link: PathBuf::from(link), target: PathBuf::from(target) })
+        })
+        .collect()
+}
+
+/// What a collection would free once `removed_links` are gone: paths that are
+/// already dead, plus the closure of the removed roots that no remaining root
+/// still needs.
+fn gc_estimate(roots: &[GcRoot], removed_links: &HashSet<PathBuf>) -> u64 {
+    let (removed, kept): (Vec<&GcRoot>, Vec<&GcRoot>) = roots.iter().partition(|root| removed_links.contains(&root.link));
+
+    // Generation links are not always reported under the path we delete, so
+    // fall back to their targets
+    let mut removed_targets: Vec<PathBuf> = removed.iter().map(|root| root.target.clone()).collect();
+    for link in removed_links {
+        if let Ok(target) = fs::canonicalize(link) {
+            removed_targets.push(target);
+        }
+    }
+    let kept_targets: Vec<PathBuf> = kept
+        .iter()
+        .map(|root| root.target.clone())
+        .filter(|target| !removed_targets.contains(target))
+        .collect();
+
+    let live = closure(&kept_targets);
+    let mut freed: HashSet<String> = closure(&removed_targets).into_iter().filter(|path| !live.contains(path)).collect();
+    freed.extend(store_query(&["--gc", "--print-dead"]));
+
+    path_sizes(&freed.into_iter().collect::<Vec<_>>())
+}
+
+fn closure_size(paths: &[PathBuf]) -> u64 {
+    path_sizes(&closure(paths).into_iter().collect::<Vec<_>>())
+}
+
+/// All store paths `paths` depend on, including themselves.
+fn closure(paths: &[PathBuf]) -> HashSet<String> {
+    paths
+        .chunks(STORE_PATH_CHUNK)
+        .flat_map(|chunk| {
+            let mut args = vec!["-qR".to_string()];
+            args.extend(chunk.iter().map(|path| path.display().to_string()));
+            store_query(&args.iter().map(String::as_str).collect::<Vec<_>>())
+        })
+        .collect()
+}
+
+/// Sum of the NAR sizes of `paths` as recorded in the Nix database.
+fn path_sizes(paths: &[String]) -> u64 {
+    paths
+        .chunks(STORE_PATH_CHUNK)
+        .flat_map(|chunk| {
+            let mut args = vec!["-q", "--size"];
+            args.extend(chunk.iter().map(String::as_str));
+            store_query(&args)
+        })
+        .filter_map(|line| line.trim().parse::<u64>().ok())
+        .sum()
+}
+
+fn store_query(args: &[&str]) -> Vec<String> {
+    match Command::new("nix-store").args(args).output() {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).lines().map(String::from).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The byte count in `nix-store --gc`'s `... deleted, 5.67 GiB freed` line.
+fn parse_freed(line: &str) -> Option<u64> {
+    let amount = line.trim().strip_suffix(" freed")?.rsplit(", ").next()?;
+    let (number, unit) = amount.split_once(' ')?;
+    let number: f64 = number.parse().ok()?;
+    let multiplier = match unit {
+        "bytes" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((number * multiplier) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_freed_reads_gc_summary() {
+        assert_eq!(parse_freed("1234 store paths deleted, 5.00 GiB freed"), Some(5 * 1024 * 1024 * 1024));
+        assert_eq!(parse_freed("0 store paths deleted, 0.00 MiB freed"), Some(0));
+        assert_eq!(parse_freed("3 store paths deleted, 512 bytes freed"), Some(512));
+        assert_eq!(parse_freed("finding garbage collector roots..."), None);
+    }
+
+    #[test]
+    fn current_generation_follows_profile_link() {
+        let dir = env::temp_dir().join(format!("spacecleaner-nix-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let profile = dir.join("profile");
+        std::os::unix::fs::symlink("profile-42-link", &profile).unwrap();
+        let unlinked = dir.join("missing");
+
+        assert_eq!(current_generation(&profile), Some(42));
+        assert_eq!(current_generation(&unlinked), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn only_build_results_are_result_links() {
+        assert!(is_result_link(Path::new("/home/me/code/app/result")));
+        assert!(is_result_link(Path::new("/home/me/code/app/result-dev")));
+        assert!(!is_result_link(Path::new("/home/me/code/app/.direnv/flake-profile-a5d5b61aa8a6")));
+        assert!(!is_result_link(Path::new("/home/me/code/app/.devenv/gc/shell")));
+    }
+}
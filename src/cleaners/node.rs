@@ -0,0 +1,293 @@
+use anyhow::Result;
+use console::style;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::Removal;
+use crate::utils::{calculate_directory_size, env_dir, format_size};
+
+/// LTS codenames as used by `.nvmrc` files such as `lts/hydrogen`.
+const LTS_CODENAMES: &[(&str, u64)] = &[
+    ("argon", 4), ("boron", 6), ("carbon", 8), ("dubnium", 10), ("erbium", 12),
+    ("fermium", 14), ("gallium", 16), ("hydrogen", 18), ("iron", 20), ("jod", 22),
+    ("krypton", 24),
+];
+
+type Version = (u64, u64, u64);
+
+struct NodeInstall {
+    manager: &'static str,
+    version: Version,
+    path: PathBuf,
+    size: u64,
+    is_default: bool,
+    pinned_by: Vec<PathBuf>,
+}
+
+struct Manager {
+    name: &'static str,
+    /// Directory holding one subdirectory per installed version
+    versions_dir: PathBuf,
+    /// The manager's default version spec, if it has one
+    default: Option<String>,
+}
+
+/// Finds Node installs made by nvm, fnm, Volta and asdf, keeps the defaults, the
+/// version currently on the PATH and those pinned by projects, and removes the rest.
+pub async fn cleanup_node_versions(dry_run: bool, yes: bool) -> Result<u64> {
+    let managers = discover_managers();
+    if managers.is_empty() {
+        println!("  {} No Node version manager found", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    let pins = find_version_pins(&crate::config::load()?.project_roots());
+    let active = active_node_version();
+    let mut removals = Vec::new();
+
+    for manager in &managers {
+        let mut installs: Vec<NodeInstall> = fs::read_dir(&manager.versions_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let version = parse_version(&entry.file_name().to_string_lossy())?;
+                Some(NodeInstall {
+                    manager: manager.name,
+                    version,
+                    size: calculate_directory_size(&entry.path()).unwrap_or(0),
+                    path: entry.path(),
+                    is_default: false,
+                    pinned_by: Vec::new(),
+                })
+            })
+            .collect();
+        installs.sort_by_key(|install| std::cmp::Reverse(install.version));
+
+        if installs.is_empty() {
+            continue;
+        }
+
+        let installed: Vec<Version> = installs.iter().map(|install| install.version).collect();
+        let default = manager.default.as_deref().map(|spec| resolve_spec(spec, &installed)).unwrap_or_default();
+        for install in installs.iter_mut() {
+            install.is_default = default.contains(&install.version) || Some(install.version) == active;
+            install.pinned_by = pins
+                .iter()
+                .filter(|(_, spec)| resolve_spec(spec, &installed).contains(&install.version))
+                .map(|(project, _)| project.clone())
+                .collect();
+        }
+
+        println!("\n  {} {}", style("⬢").green(), style(manager.name).bold());
+        for install in &installs {
+            let note = if install.is_default {
+                style("default/active".to_string()).green()
+            } else if !install.pinned_by.is_empty() {
+                style(format!("pinned by {}", install.pinned_by[0].display())).green()
+            } else {
+                style("unused".to_string()).yellow()
+            };
+            println!("    v{:<12} {:>12}  {}", format_version(install.version), format_size(install.size), note);
+        }
+
+        removals.extend(installs
+            .into_iter()
+            .filter(|install| !install.is_default && install.pinned_by.is_empty())
+            .map(|install| Removal {
+                description: format!("{} v{}", install.manager, format_version(install.version)),
+                path: install.path,
+                size: install.size,
+            }));
+    }
+
+    println!();
+    super::remove_with_confirmation("unused Node versions", &removals, dry_run, yes)
+}
+
+fn discover_managers() -> Vec<Manager> {
+    let home = PathBuf::from(shellexpand::tilde("~").as_ref());
+    let mut managers = Vec::new();
+
+    // nvm: versions/node/vX.Y.Z, default in alias/default
+    let nvm_dir = env_dir("NVM_DIR").unwrap_or_else(|| home.join(".nvm"));
+    if nvm_dir.join("versions/node").is_dir() {
+        managers.push(Manager {
+            name: "nvm",
+            versions_dir: nvm_dir.join("versions/node"),
+            default: read_first_line(&nvm_dir.join("alias/default")),
+        });
+    }
+
+    // fnm: node-versions/vX.Y.Z, default is the aliases/default symlink
+    let fnm_dirs = [
+        env_dir("FNM_DIR"),
+        Some(home.join(".local/share/fnm")),
+        Some(home.join("Library/Application Support/fnm")),
+        Some(home.join(".fnm")),
+    ];
+    if let Some(fnm_dir) = fnm_dirs.into_iter().flatten().find(|dir| dir.join("node-versions").is_dir()) {
+        let default = fs::read_link(fnm_dir.join("aliases/default")).ok().and_then(|target| {
+            target
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .find(|component| parse_version(component).is_some())
+        });
+        managers.push(Manager {
+            name: "fnm",
+            versions_dir: fnm_dir.join("node-versions"),
+            default,
+        });
+    }
+
+    // Volta: tools/image/node/X.Y.Z, default in tools/user/platform.json
+    let volta_dir = env_dir("VOLTA_HOME").unwrap_or_else(|| home.join(".volta"));
+    if volta_dir.join("tools/image/node").is_dir() {
+        let default = fs::read_to_string(volta_dir.join("tools/user/platform.json"))
+            .ok()
+            .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+            .and_then(|platform| platform["node"]["runtime"].as_str().map(String::from));
+        managers.push(Manager {
+            name: "volta",
+            versions_dir: volta_dir.join("tools/image/node"),
+            default,
+        });
+    }
+
+    // asdf: installs/nodejs/X.Y.Z, default in ~/.tool-versions
+    let asdf_dir = env_dir("ASDF_DATA_DIR").unwrap_or_else(|| home.join(".asdf"));
+    if asdf_dir.join("installs/nodejs").is_dir() {
+        managers.push(Manager {
+            name: "asdf",
+            versions_dir: asdf_dir.join("installs/nodejs"),
+            default: read_tool_versions(&home.join(".tool-versions")),
+        });
+    }
+
+    managers
+}
+
+/// Version specs from `.nvmrc`, `.node-version`, `.tool-versions` and the `volta`
+/// section of `package.json`, keyed by project directory.
+fn find_version_pins(roots: &[PathBuf]) -> Vec<(PathBuf, String)> {
+    let mut pins = Vec::new();
+
+    for root in roots {
+        for entry in crate::utils::walk_project_root(root) {
+            let Some(project) = entry.path().parent() else { continue };
+            let spec = match entry.file_name().to_str() {
+                Some(".nvmrc") | Some(".node-version") => read_first_line(entry.path()),
+                Some(".tool-versions") => read_tool_versions(entry.path()),
+                Some("package.json") => fs::read_to_string(entry.path())
+                    .ok()
+                    .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+                    .and_then(|package| package["volta"]["node"].as_str().map(String::from)),
+                _ => None,
+            };
+            if let Some(spec) = spec {
+                pins.push((project.to_path_buf(), spec));
+            }
+        }
+    }
+
+    pins
+}
+
+/// The version of `node` on the PATH, which is what the user's shell is using now.
+fn active_node_version() -> Option<Version> {
+    let output = Command::new("node").arg("--version").output().ok()?;
+    parse_version(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// Resolves a spec such as `v18.17.0`, `18`, `18.17`, `lts/hydrogen` or `node`
+/// to the installed versions a version manager could pick: the newest match, or
+/// every LTS line for a codename this table does not know yet.
+fn resolve_spec(spec: &str, installed: &[Version]) -> Vec<Version> {
+    let spec = spec.trim().trim_start_matches('v');
+    let newest = |matches: &dyn Fn(&Version) -> bool| installed.iter().filter(|v| matches(v)).max().copied();
+    // Even majors from 4 on are the LTS lines
+    let is_lts = |version: &Version| version.0 >= 4 && version.0.is_multiple_of(2);
+
+    if spec == "node" || spec == "latest" || spec == "stable" {
+        return newest(&|_| true).into_iter().collect();
+    }
+
+    if spec == "lts/*" {
+        return newest(&is_lts).into_iter().collect();
+    }
+
+    if let Some(codename) = spec.strip_prefix("lts/") {
+        return match LTS_CODENAMES.iter().find(|(name, _)| name.eq_ignore_ascii_case(codename)) {
+            Some((_, major)) => newest(&|version| version.0 == *major).into_iter().collect(),
+            None => installed.iter().filter(|version| is_lts(version)).copied().collect(),
+        };
+    }
+
+    let parts: Vec<u64> = spec.split('.').map_while(|part| part.parse().ok()).collect();
+    let resolved = match parts.as_slice() {
+        [major] => newest(&|version| version.0 == *major),
+        [major, minor] => newest(&|version| version.0 == *major && version.1 == *minor),
+        [major, minor, patch, ..] => newest(&|version| *version == (*major, *minor, *patch)),
+        _ => None,
+    };
+    resolved.into_iter().collect()
+}
+
+fn parse_version(name: &str) -> Option<Version> {
+    let mut parts = name.trim_start_matches('v').split('.').map(|part| part.parse::<u64>().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+fn format_version(version: Version) -> String {
+    format!("{}.{}.{}", version.0, version.1, version.2)
+}
+
+fn read_first_line(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+    let line = contents.lines().next()?.trim();
+    (!line.is_empty()).then(|| line.to_string())
+}
+
+/// The `nodejs` entry of an asdf `.tool-versions` file.
+fn read_tool_versions(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()?
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("nodejs ")?.split_whitespace().next().map(String::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INSTALLED: &[Version] = &[(16, 20, 2), (18, 17, 0), (18, 19, 1), (20, 11, 0), (21, 6, 1)];
+
+    #[test]
+    fn resolves_partial_versions_to_newest_match() {
+        assert_eq!(resolve_spec("18", INSTALLED), vec![(18, 19, 1)]);
+        assert_eq!(resolve_spec("v18.17", INSTALLED), vec![(18, 17, 0)]);
+        assert_eq!(resolve_spec("18.17.0", INSTALLED), vec![(18, 17, 0)]);
+        assert_eq!(resolve_spec("19", INSTALLED), Vec::<Version>::new());
+        assert_eq!(resolve_spec("node", INSTALLED), vec![(21, 6, 1)]);
+    }
+
+    #[test]
+    fn lts_star_picks_newest_even_major() {
+        assert_eq!(resolve_spec("lts/*", INSTALLED), vec![(20, 11, 0)]);
+    }
+
+    #[test]
+    fn lts_codenames_map_to_their_major() {
+        assert_eq!(resolve_spec("lts/hydrogen", INSTALLED), vec![(18, 19, 1)]);
+        assert_eq!(resolve_spec("lts/Gallium", INSTALLED), vec![(16, 20, 2)]);
+    }
+
+    #[test]
+    fn unknown_lts_codename_protects_every_lts_line() {
+        assert_eq!(
+            resolve_spec("lts/zinc", INSTALLED),
+            vec![(16, 20, 2), (18, 17, 0), (18, 19, 1), (20, 11, 0)]
+        );
+    }
+}
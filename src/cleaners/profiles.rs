@@ -0,0 +1,149 @@
+use anyhow::Result;
+use console::style;
+use serde::Deserialize;
+
+use super::docker::DockerOptions;
+
+/// A named set of cleanup targets, either built in or defined in the config file.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Profile {
+    #[serde(default)]
+    pub description: String,
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub retention: Retention,
+    #[serde(default)]
+    pub docker: DockerOptions,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Retention {
+    /// Only remove entries that have not been modified for this many days.
+    /// Targets that cannot tell the age of their entries ignore it.
+    pub min_age_days: Option<u64>,
+    /// Size limit in MiB that size-capped caches such as ccache are trimmed to.
+    pub max_cache_size_mb: Option<u64>,
+}
+
+/// Profiles that exist without any configuration. The config file can override them.
+pub fn builtin_profiles() -> Vec<(&'static str, Profile)> {
+    let quick = vec!["homebrew", "pip", "npm", "composer", "node-gyp"];
+
+    let mut caches = quick.clone();
+    caches.extend(["playwright", "browsers", "temp"]);
+
+    vec![
+        ("quick", Profile {
+            description: "Safe cache directories that can be easily regenerated".to_string(),
+            targets: quick.into_iter().map(String::from).collect(),
+            ..Profile::default()
+        }),
+        ("caches", Profile {
+            description: "All cache directories".to_string(),
+            targets: caches.into_iter().map(String::from).collect(),
+            ..Profile::default()
+        }),
+        ("docker", Profile {
+            description: "Unused Docker images, containers and build cache".to_string(),
+            targets: vec!["docker".to_string()],
+            ..Profile::default()
+        }),
+        ("system", Profile {
+            description: "System package caches and logs (Linux, needs root and --system)".to_string(),
+            targets: vec!["packages".to_string(), "system-logs".to_string()],
+            ..Profile::default()
+        }),
+    ]
+}
+
+/// Looks up a profile by name, preferring the config file over the built-in ones.
+pub fn find_profile(name: &str) -> Result<Profile> {
+    let config = crate::config::load()?;
+
+    if let Some(profile) = config.profiles.get(name) {
+        return Ok(profile.clone());
+    }
+
+    builtin_profiles()
+        .into_iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, profile)| profile)
+        .ok_or_else(|| anyhow::anyhow!(
+            "Unknown profile '{}' (run `spacecleaner run` to list profiles)",
+            name
+        ))
+}
+
+pub fn list_profiles() -> Result<()> {
+    let config = crate::config::load()?;
+
+    println!("{}", style("📋 Cleanup Profiles").bold().cyan());
+
+    let mut names: Vec<_> = config.profiles.keys().cloned().collect();
+    for (builtin, _) in builtin_profiles() {
+        if !names.iter().any(|name| name == builtin) {
+            names.push(builtin.to_string());
+        }
+    }
+    names.sort();
+
+    for name in names {
+        let profile = find_profile(&name)?;
+        let origin = if config.profiles.contains_key(&name) { "config" } else { "built-in" };
+        println!("  {:<20} {} {}",
+            style(&name).cyan(),
+            profile.description,
+            style(format!("({})", origin)).dim()
+        );
+        println!("  {:<20} {}", "", style(profile.targets.join(", ")).dim());
+    }
+
+    println!("\n{} Define your own in {}",
+        style("💡").dim(),
+        crate::config::config_path().display()
+    );
+
+    Ok(())
+}
+
+pub async fn run_named_profile(name: &str, dry_run: bool, yes: bool) -> Result<u64> {
+    let profile = find_profile(name)?;
+
+    println!("{}", style(format!("🚀 Running '{}' profile...", name)).bold().green());
+    if !profile.description.is_empty() {
+        println!("{}\n", profile.description);
+    }
+
+    run_profile(name, &profile, dry_run, yes).await
+}
+
+/// Runs every target of the profile in order and returns the total bytes freed.
+pub async fn run_profile(name: &str, profile: &Profile, dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    for target in &profile.targets {
+        println!("{} {}...", style("🧹").cyan(), super::target_label(target));
+        match super::run_target(target, profile, dry_run, yes).await {
+            Ok(size) => {
+                if size > 0 {
+                    println!("  {} {}", style("✓").green(), crate::utils::format_size(size));
+                    total_freed += size;
+                } else {
+                    println!("  {} Nothing to clean", style("ℹ").blue());
+                }
+            }
+            Err(e) => println!("  {} Error: {}", style("✗").red(), e),
+        }
+    }
+
+    if total_freed > 0 {
+        println!("\n{} Total freed: {}",
+            style("🎉").green(),
+            crate::utils::format_size(total_freed)
+        );
+    }
+
+    crate::history::record_cleanup_or_warn(name, total_freed, dry_run);
+
+    Ok(total_freed)
+}
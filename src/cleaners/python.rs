@@ -0,0 +1,387 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::Confirm;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::Removal;
+use crate::utils::{cache_home, calculate_directory_size, command_exists, days_ago, format_size, last_used, last_used_in, read_dirs};
+
+/// Environments unused for this long are offered for removal by default.
+const DEFAULT_MAX_UNUSED_DAYS: u64 = 90;
+
+/// Tool caches that are safe to delete in projects; they are rebuilt on the next run.
+const PROJECT_CACHE_DIRS: &[&str] = &["__pycache__", ".pytest_cache", ".mypy_cache", ".ruff_cache"];
+
+/// Conda installs commonly found in the home directory.
+const CONDA_ROOTS: &[&str] = &[
+    "~/miniconda3", "~/anaconda3", "~/miniforge3", "~/mambaforge", "~/micromamba", "~/.conda",
+];
+
+/// Runs every Python cleaner in turn.
+pub async fn cleanup_python(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    println!("  {} pyenv versions", style("🐍").cyan());
+    total_freed += cleanup_pyenv_versions(dry_run, yes).await?;
+    println!("  {} conda packages and environments", style("🐍").cyan());
+    total_freed += cleanup_conda(min_age_days, dry_run, yes).await?;
+    println!("  {} Poetry cache and virtualenvs", style("🐍").cyan());
+    total_freed += cleanup_poetry(min_age_days, dry_run, yes).await?;
+    println!("  {} uv cache", style("🐍").cyan());
+    total_freed += cleanup_uv(dry_run, yes).await?;
+    println!("  {} pipx shared libraries", style("🐍").cyan());
+    total_freed += cleanup_pipx(dry_run, yes).await?;
+    println!("  {} Bytecode and tool caches in projects", style("🐍").cyan());
+    total_freed += cleanup_project_caches(dry_run, yes).await?;
+
+    Ok(total_freed)
+}
+
+/// Removes pyenv versions that are neither the global version nor pinned by a
+/// `.python-version` file under the project roots.
+pub async fn cleanup_pyenv_versions(dry_run: bool, yes: bool) -> Result<u64> {
+    let pyenv_root = env::var("PYENV_ROOT")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/.pyenv").as_ref()));
+    let versions_dir = pyenv_root.join("versions");
+
+    if !versions_dir.is_dir() {
+        println!("    {} pyenv not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let mut installed: Vec<String> = Vec::new();
+    // pyenv-virtualenv aliases: versions/<name> -> versions/<base>/envs/<name>
+    let mut aliases: Vec<(String, String)> = Vec::new();
+    for entry in fs::read_dir(&versions_dir)?.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if entry.file_type().is_ok_and(|t| t.is_symlink()) {
+            if let Some(base) = virtualenv_base(&versions_dir, &entry.path()) {
+                aliases.push((name, base));
+            }
+        } else if entry.file_type().is_ok_and(|t| t.is_dir()) {
+            installed.push(name);
+        }
+    }
+
+    let mut specs = read_python_versions(&pyenv_root.join("version"));
+    for root in crate::config::load()?.project_roots() {
+        for entry in crate::utils::walk_project_root(&root) {
+            if entry.file_name() == ".python-version" {
+                specs.extend(read_python_versions(entry.path()));
+            }
+        }
+    }
+
+    // A virtualenv, by alias or as `<base>/envs/<name>`, keeps its base version
+    let kept: Vec<&String> = specs
+        .iter()
+        .filter_map(|spec| {
+            let base = aliases
+                .iter()
+                .find(|(alias, _)| alias == spec)
+                .map(|(_, base)| base.as_str())
+                .or_else(|| spec.split_once("/envs/").map(|(base, _)| base))
+                .unwrap_or(spec);
+            resolve_python_spec(base, &installed)
+        })
+        .collect();
+
+    let mut removals: Vec<Removal> = Vec::new();
+    for version in installed.iter().filter(|version| !kept.contains(version)) {
+        let path = versions_dir.join(version);
+        removals.push(Removal {
+            description: format!("pyenv {}", version),
+            size: calculate_directory_size(&path).unwrap_or(0),
+            path,
+        });
+        // The aliases would dangle once their base version is gone
+        for (alias, _) in aliases.iter().filter(|(_, base)| base == version) {
+            removals.push(Removal {
+                description: format!("pyenv virtualenv alias {}", alias),
+                path: versions_dir.join(alias),
+                size: 0,
+            });
+        }
+    }
+
+    for version in &installed {
+        let note = if kept.contains(&version) { style("in use").green() } else { style("unused").yellow() };
+        println!("    {:<20} {}", version, note);
+    }
+    for (alias, base) in &aliases {
+        let note = if kept.contains(&base) { style("in use").green() } else { style("unused").yellow() };
+        println!("    {:<20} {} {}", alias, style(format!("-> {}/envs/{}", base, alias)).dim(), note);
+    }
+
+    super::remove_with_confirmation("unused pyenv versions", &removals, dry_run, yes)
+}
+
+/// Cleans conda's package cache and removes environments unused for `min_age_days`.
+pub async fn cleanup_conda(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let roots: Vec<PathBuf> = CONDA_ROOTS
+        .iter()
+        .map(|root| PathBuf::from(shellexpand::tilde(root).as_ref()))
+        .filter(|root| root.join("pkgs").is_dir() || root.join("envs").is_dir())
+        .collect();
+
+    if roots.is_empty() {
+        println!("    {} conda not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let mut total_freed = 0u64;
+    let conda = ["mamba", "conda"].into_iter().find(|tool| command_exists(tool));
+
+    for root in &roots {
+        let pkgs = root.join("pkgs");
+        if !pkgs.is_dir() {
+            continue;
+        }
+
+        let size_before = calculate_directory_size(&pkgs)?;
+        if size_before == 0 {
+            continue;
+        }
+
+        if let Some(conda) = conda {
+            // conda knows which extracted packages are still linked into environments
+            if dry_run {
+                println!("    Would run `{} clean --all` on {}: up to {}", conda, pkgs.display(), format_size(size_before));
+                total_freed += size_before;
+            } else if yes || Confirm::new()
+                .with_prompt(format!("Run `{} clean --all`? ({} package cache)", conda, format_size(size_before)))
+                .default(true)
+                .interact()?
+            {
+                Command::new(conda).args(["clean", "--all", "--yes"]).output()?;
+                let size_after = calculate_directory_size(&pkgs).unwrap_or(0);
+                total_freed += size_before.saturating_sub(size_after);
+            }
+        } else {
+            // Without conda only the downloaded archives are safe to drop; extracted
+            // packages may be linked into environments
+            let archives: Vec<Removal> = fs::read_dir(&pkgs)?
+                .flatten()
+                .filter(|entry| {
+                    let name = entry.file_name().to_string_lossy().into_owned();
+                    name.ends_with(".tar.bz2") || name.ends_with(".conda")
+                })
+                .map(|entry| Removal {
+                    description: entry.file_name().to_string_lossy().into_owned(),
+                    size: entry.metadata().map(|m| m.len()).unwrap_or(0),
+                    path: entry.path(),
+                })
+                .collect();
+            total_freed += super::remove_with_confirmation("conda package archives", &archives, dry_run, yes)?;
+        }
+    }
+
+    let days = min_age_days.unwrap_or(DEFAULT_MAX_UNUSED_DAYS);
+    let cutoff = days_ago(days);
+    let mut stale_envs = Vec::new();
+
+    for root in &roots {
+        for env_dir in read_dirs(&root.join("envs")) {
+            // conda-meta/history changes on every install; the interpreter's atime on every run
+            let used = [env_dir.join("conda-meta/history"), env_dir.join("bin/python")]
+                .iter()
+                .filter_map(|path| fs::metadata(path).ok())
+                .map(|metadata| last_used(&metadata))
+                .max();
+
+            if used.is_some_and(|used| used < cutoff) {
+                stale_envs.push(Removal {
+                    description: format!("conda env {}", env_dir.file_name().unwrap_or_default().to_string_lossy()),
+                    size: calculate_directory_size(&env_dir).unwrap_or(0),
+                    path: env_dir,
+                });
+            }
+        }
+    }
+
+    if !stale_envs.is_empty() {
+        println!("    Environments unused for {} days:", days);
+    }
+    total_freed += super::remove_with_confirmation("unused conda environments", &stale_envs, dry_run, yes)?;
+
+    Ok(total_freed)
+}
+
+/// Cleans Poetry's package caches and removes virtualenvs unused for `min_age_days`.
+pub async fn cleanup_poetry(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let poetry_dir = env::var("POETRY_CACHE_DIR")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| cache_home().join("pypoetry"));
+
+    if !poetry_dir.is_dir() {
+        println!("    {} Poetry cache not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let mut removals: Vec<Removal> = ["cache", "artifacts"]
+        .iter()
+        .map(|name| poetry_dir.join(name))
+        .filter(|path| path.is_dir())
+        .map(|path| Removal {
+            description: format!("Poetry {}", path.file_name().unwrap_or_default().to_string_lossy()),
+            size: calculate_directory_size(&path).unwrap_or(0),
+            path,
+        })
+        .collect();
+
+    // Virtualenv names hash the project path, so age is the only usable signal
+    let cutoff = days_ago(min_age_days.unwrap_or(DEFAULT_MAX_UNUSED_DAYS));
+    for venv in read_dirs(&poetry_dir.join("virtualenvs")) {
+        // bin/python is a symlink to the base interpreter, whose times say nothing about this venv
+        let used = fs::symlink_metadata(venv.join("bin/python")).map(|m| last_used(&m)).unwrap_or_else(|_| last_used_in(&venv));
+        if used < cutoff {
+            removals.push(Removal {
+                description: format!("Poetry virtualenv {}", venv.file_name().unwrap_or_default().to_string_lossy()),
+                size: calculate_directory_size(&venv).unwrap_or(0),
+                path: venv,
+            });
+        }
+    }
+
+    super::remove_with_confirmation("Poetry caches", &removals, dry_run, yes)
+}
+
+/// Cleans uv's cache, preferring `uv cache clean` so uv's own locking is respected.
+pub async fn cleanup_uv(dry_run: bool, yes: bool) -> Result<u64> {
+    let cache_dir = env::var("UV_CACHE_DIR")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| cache_home().join("uv"));
+
+    if !cache_dir.is_dir() {
+        println!("    {} uv cache not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let size_before = calculate_directory_size(&cache_dir)?;
+    if size_before == 0 {
+        return Ok(0);
+    }
+
+    if dry_run {
+        println!("    Would clean uv cache: {}", format_size(size_before));
+        return Ok(size_before);
+    }
+
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Clean uv cache? ({})", format_size(size_before)))
+        .default(true)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    if command_exists("uv") {
+        Command::new("uv").args(["cache", "clean"]).output()?;
+    } else {
+        crate::utils::remove_path(&cache_dir)?;
+    }
+
+    let size_after = if cache_dir.exists() { calculate_directory_size(&cache_dir)? } else { 0 };
+    Ok(size_before.saturating_sub(size_after))
+}
+
+/// Removes pipx's shared pip/setuptools libraries and its `pipx run` cache. pipx
+/// recreates the shared libraries the next time it needs them.
+pub async fn cleanup_pipx(dry_run: bool, yes: bool) -> Result<u64> {
+    let candidates = [
+        env::var("PIPX_HOME").ok().filter(|dir| !dir.is_empty()).map(PathBuf::from),
+        Some(PathBuf::from(shellexpand::tilde("~/.local/pipx").as_ref())),
+        Some(PathBuf::from(shellexpand::tilde("~/.local/share/pipx").as_ref())),
+        Some(PathBuf::from(shellexpand::tilde("~/Library/Application Support/pipx").as_ref())),
+    ];
+
+    let Some(pipx_home) = candidates.into_iter().flatten().find(|dir| dir.is_dir()) else {
+        println!("    {} pipx not found", style("ℹ").dim());
+        return Ok(0);
+    };
+
+    let removals: Vec<Removal> = ["shared", ".cache"]
+        .iter()
+        .map(|name| pipx_home.join(name))
+        .filter(|path| path.is_dir())
+        .map(|path| Removal {
+            description: format!("pipx {}", path.file_name().unwrap_or_default().to_string_lossy()),
+            size: calculate_directory_size(&path).unwrap_or(0),
+            path,
+        })
+        .collect();
+
+    super::remove_with_confirmation("pipx shared libraries and cache", &removals, dry_run, yes)
+}
+
+/// Removes `__pycache__`, `.pytest_cache`, `.mypy_cache` and `.ruff_cache`
+/// directories under the project roots.
+pub async fn cleanup_project_caches(dry_run: bool, yes: bool) -> Result<u64> {
+    let mut removals = Vec::new();
+
+    for root in crate::config::load()?.project_roots() {
+        for entry in crate::utils::walk_project_root(&root) {
+            let name = entry.file_name().to_string_lossy();
+            if entry.file_type().is_dir() && PROJECT_CACHE_DIRS.contains(&name.as_ref()) {
+                removals.push(Removal {
+                    description: entry.path().display().to_string(),
+                    size: calculate_directory_size(entry.path()).unwrap_or(0),
+                    path: entry.path().to_path_buf(),
+                });
+            }
+        }
+    }
+
+    super::remove_with_confirmation("Python tool caches", &removals, dry_run, yes)
+}
+
+/// Version names listed in a pyenv `version` or `.python-version` file.
+fn read_python_versions(path: &Path) -> Vec<String> {
+    fs::read_to_string(path)
+        .map(|contents| {
+            contents
+                .lines()
+                .map(|line| line.trim().to_string())
+                .filter(|line| !line.is_empty() && !line.starts_with('#') && line != "system")
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The base version of a pyenv-virtualenv alias, if `alias` links to
+/// `versions/<base>/envs/<name>`.
+fn virtualenv_base(versions_dir: &Path, alias: &Path) -> Option<String> {
+    let target = fs::canonicalize(alias).ok()?;
+    let relative = target.strip_prefix(fs::canonicalize(versions_dir).ok()?).ok()?;
+    let mut components = relative.components();
+    let base = components.next()?.as_os_str().to_string_lossy().into_owned();
+    (components.next()?.as_os_str() == "envs").then_some(base)
+}
+
+/// Resolves a spec to an installed version: an exact name, or the newest version
+/// with that prefix (`3.11` -> `3.11.9`) the way pyenv does.
+fn resolve_python_spec<'a>(spec: &str, installed: &'a [String]) -> Option<&'a String> {
+    if let Some(exact) = installed.iter().find(|version| *version == spec) {
+        return Some(exact);
+    }
+
+    installed
+        .iter()
+        .filter(|version| version.strip_prefix(spec).is_some_and(|rest| rest.starts_with('.')))
+        .max_by_key(|version| {
+            version
+                .split('.')
+                .map(|part| part.parse::<u64>().unwrap_or(0))
+                .collect::<Vec<_>>()
+        })
+}
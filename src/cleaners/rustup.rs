@@ -0,0 +1,207 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::Confirm;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::utils::{calculate_directory_size, format_size};
+
+struct Toolchain {
+    name: String,
+    size: u64,
+    is_default: bool,
+    is_active: bool,
+    /// Projects whose toolchain file or rustup override pins this toolchain
+    referenced_by: Vec<PathBuf>,
+}
+
+/// Lists installed rustup toolchains with their sizes and the projects that still
+/// pin them, and offers to uninstall the ones nothing references.
+pub async fn cleanup_rustup_toolchains(dry_run: bool, yes: bool) -> Result<u64> {
+    let Some(installed) = list_toolchains() else {
+        println!("  {} rustup not found", style("ℹ").blue());
+        return Ok(0);
+    };
+
+    let toolchains_dir = rustup_home().join("toolchains");
+    let pins = find_toolchain_pins(&crate::config::load()?.project_roots());
+
+    let mut toolchains: Vec<Toolchain> = installed
+        .into_iter()
+        .map(|(name, is_default, is_active)| {
+            let referenced_by = pins
+                .iter()
+                .filter(|(_, channel)| toolchain_matches(&name, channel))
+                .map(|(project, _)| project.clone())
+                .collect();
+            Toolchain {
+                size: calculate_directory_size(&toolchains_dir.join(&name)).unwrap_or(0),
+                name,
+                is_default,
+                is_active,
+                referenced_by,
+            }
+        })
+        .collect();
+    toolchains.sort_by_key(|toolchain| std::cmp::Reverse(toolchain.size));
+
+    for toolchain in &toolchains {
+        let mut notes = Vec::new();
+        if toolchain.is_default {
+            notes.push("default".to_string());
+        }
+        if toolchain.is_active {
+            notes.push("active".to_string());
+        }
+        if !toolchain.referenced_by.is_empty() {
+            notes.push(format!("used by {} project(s)", toolchain.referenced_by.len()));
+        }
+        let notes = if notes.is_empty() {
+            style("unreferenced".to_string()).yellow()
+        } else {
+            style(notes.join(", ")).green()
+        };
+
+        println!("  {:<45} {:>12}  {}", toolchain.name, format_size(toolchain.size), notes);
+        for project in toolchain.referenced_by.iter().take(3) {
+            println!("  {:<45} {:>12}  {}", "", "", style(project.display()).dim());
+        }
+    }
+
+    let unreferenced: Vec<&Toolchain> = toolchains
+        .iter()
+        .filter(|toolchain| !toolchain.is_default && !toolchain.is_active && toolchain.referenced_by.is_empty())
+        .collect();
+
+    if unreferenced.is_empty() {
+        println!("  {} Every toolchain is in use", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    let mut total_freed = 0u64;
+
+    for toolchain in unreferenced {
+        if dry_run {
+            println!("  Would uninstall {}: {}", toolchain.name, format_size(toolchain.size));
+            total_freed += toolchain.size;
+            continue;
+        }
+
+        if yes || Confirm::new()
+            .with_prompt(format!("Uninstall toolchain {}? ({})", toolchain.name, format_size(toolchain.size)))
+            .default(false)
+            .interact()?
+        {
+            let output = Command::new("rustup")
+                .args(["toolchain", "uninstall", &toolchain.name])
+                .output()?;
+
+            if output.status.success() {
+                total_freed += toolchain.size;
+            } else {
+                println!("  {} Could not uninstall {}: {}",
+                    style("✗").red(),
+                    toolchain.name,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                );
+            }
+        }
+    }
+
+    Ok(total_freed)
+}
+
+fn rustup_home() -> PathBuf {
+    match env::var("RUSTUP_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(shellexpand::tilde("~/.rustup").as_ref()),
+    }
+}
+
+/// Installed toolchains as `(name, is_default, is_active)`.
+fn list_toolchains() -> Option<Vec<(String, bool, bool)>> {
+    let output = Command::new("rustup").args(["toolchain", "list"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Lines look like `stable-x86_64-unknown-linux-gnu (active, default)`
+    let toolchains = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let name = line.split_whitespace().next()?.to_string();
+            let flags = line[name.len()..].to_string();
+            Some((name, flags.contains("default"), flags.contains("active")))
+        })
+        .filter(|(name, _, _)| name != "no")
+        .collect();
+
+    Some(toolchains)
+}
+
+/// Channels pinned by `rust-toolchain.toml`/`rust-toolchain` files under the
+/// project roots and by `rustup override`, keyed by project directory.
+fn find_toolchain_pins(roots: &[PathBuf]) -> Vec<(PathBuf, String)> {
+    let mut pins = Vec::new();
+
+    for root in roots {
+        for entry in crate::utils::walk_project_root(root) {
+            let name = entry.file_name();
+            if name != "rust-toolchain.toml" && name != "rust-toolchain" {
+                continue;
+            }
+            if let (Some(project), Some(channel)) = (entry.path().parent(), read_toolchain_file(entry.path())) {
+                pins.push((project.to_path_buf(), channel));
+            }
+        }
+    }
+
+    // `rustup override list` prints `<directory>  <toolchain>` per line
+    if let Ok(output) = Command::new("rustup").args(["override", "list"]).output() {
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let mut parts = line.split_whitespace();
+            if let (Some(directory), Some(toolchain)) = (parts.next(), parts.next()) {
+                if Path::new(directory).exists() {
+                    pins.push((PathBuf::from(directory), toolchain.to_string()));
+                }
+            }
+        }
+    }
+
+    pins
+}
+
+/// Reads the channel from either the TOML form (`[toolchain] channel = "..."`)
+/// or the legacy single-line form.
+fn read_toolchain_file(path: &Path) -> Option<String> {
+    let contents = fs::read_to_string(path).ok()?;
+
+    if let Ok(parsed) = toml::from_str::<HashMap<String, toml::Value>>(&contents) {
+        if let Some(channel) = parsed
+            .get("toolchain")
+            .and_then(|toolchain| toolchain.get("channel"))
+            .and_then(|channel| channel.as_str())
+        {
+            return Some(channel.to_string());
+        }
+    }
+
+    let line = contents.lines().next()?.trim();
+    (!line.is_empty() && !line.starts_with('[')).then(|| line.to_string())
+}
+
+/// Whether an installed toolchain such as `nightly-2024-01-01-x86_64-apple-darwin`
+/// satisfies a pinned channel such as `nightly-2024-01-01`.
+fn toolchain_matches(installed: &str, channel: &str) -> bool {
+    // The remainder must be the host triple, which starts with the architecture
+    // (`x86_64`, `aarch64`, ...) rather than a date like `2024-01-01`
+    installed == channel
+        || installed
+            .strip_prefix(channel)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .and_then(|triple| triple.chars().next())
+            .is_some_and(|c| c.is_ascii_alphabetic())
+}
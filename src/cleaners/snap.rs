@@ -0,0 +1,96 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::Confirm;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::utils::format_size;
+
+/// Where snapd keeps the squashfs image of every installed revision.
+const SNAPS_DIR: &str = "/var/lib/snapd/snaps";
+
+pub struct SnapRevision {
+    pub name: String,
+    pub revision: String,
+    pub size: u64,
+}
+
+/// Removes the old revisions snapd keeps disabled after each refresh, using
+/// `snap remove --revision`.
+pub async fn cleanup_snap_revisions(dry_run: bool, yes: bool) -> Result<u64> {
+    let Some(revisions) = disabled_revisions() else {
+        println!("  {} snap not found", style("ℹ").dim());
+        return Ok(0);
+    };
+
+    if revisions.is_empty() {
+        println!("  {} No disabled snap revisions", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    let total: u64 = revisions.iter().map(|revision| revision.size).sum();
+    for revision in &revisions {
+        println!("    {:<30} rev {:<8} {:>12}", revision.name, revision.revision, format_size(revision.size));
+    }
+
+    if dry_run {
+        println!("  Would remove {} disabled snap revisions: {}", revisions.len(), format_size(total));
+        return Ok(total);
+    }
+
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Remove {} disabled snap revisions? ({})", revisions.len(), format_size(total)))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    let mut total_freed = 0u64;
+    for revision in revisions {
+        let output = Command::new("snap")
+            .args(["remove", &revision.name, &format!("--revision={}", revision.revision)])
+            .output()?;
+
+        if output.status.success() {
+            total_freed += revision.size;
+        } else {
+            println!("  {} Could not remove {} rev {}: {}",
+                style("✗").red(),
+                revision.name,
+                revision.revision,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+
+    Ok(total_freed)
+}
+
+/// Disabled revisions from `snap list --all`, or `None` when snap is unavailable.
+pub fn disabled_revisions() -> Option<Vec<SnapRevision>> {
+    let output = Command::new("snap").args(["list", "--all"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Name  Version  Rev  Tracking  Publisher  Notes
+    let revisions = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 6 || !fields[fields.len() - 1].split(',').any(|note| note == "disabled") {
+                return None;
+            }
+            let (name, revision) = (fields[0].to_string(), fields[2].to_string());
+            let size = fs::metadata(Path::new(SNAPS_DIR).join(format!("{}_{}.snap", name, revision)))
+                .map(|metadata| metadata.len())
+                .unwrap_or(0);
+            Some(SnapRevision { name, revision, size })
+        })
+        .collect();
+
+    Some(revisions)
+}
@@ -0,0 +1,381 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::Confirm;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use walkdir::WalkDir;
+
+use super::Removal;
+use crate::utils::{command_exists, format_size};
+
+/// How many versions of each package pacman's cache keeps, like `paccache -r`.
+const PACMAN_KEEP_VERSIONS: usize = 3;
+
+/// Set from the `--system` flag; system-wide cleaners refuse to run without it.
+static SYSTEM_ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn enable_system_cleaners() {
+    SYSTEM_ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether system-wide cleaners may run: on Linux, with `--system`, as root.
+/// Explains why not when they may not.
+pub fn system_cleaners_allowed() -> bool {
+    if !cfg!(target_os = "linux") {
+        println!("  {} System cleaners are only available on Linux", style("ℹ").blue());
+        return false;
+    }
+    if !SYSTEM_ENABLED.load(Ordering::Relaxed) {
+        println!("  {} System cleaners are disabled; pass --system to enable them", style("ℹ").blue());
+        return false;
+    }
+    if !crate::utils::is_root() {
+        println!("  {} System cleaners need root; run with sudo", style("ℹ").blue());
+        return false;
+    }
+    true
+}
+
+/// Cleans the download caches of every package manager found on the system.
+pub async fn cleanup_package_caches(dry_run: bool, yes: bool) -> Result<u64> {
+    if !system_cleaners_allowed() {
+        return Ok(0);
+    }
+
+    let mut total_freed = 0u64;
+    total_freed += cleanup_apt(dry_run, yes).await?;
+    total_freed += cleanup_dnf(dry_run, yes).await?;
+    total_freed += cleanup_pacman(dry_run, yes).await?;
+    total_freed += cleanup_zypper(dry_run, yes).await?;
+
+    Ok(total_freed)
+}
+
+/// Downloaded `.deb` archives, removed with `apt-get clean` so apt's lock is
+/// respected. Package lists are left in place.
+pub async fn cleanup_apt(dry_run: bool, yes: bool) -> Result<u64> {
+    let archives = Path::new("/var/cache/apt/archives");
+    if !archives.exists() || !system_cleaners_allowed() {
+        return Ok(0);
+    }
+
+    println!("  {} apt", style("📦").cyan());
+    let removals = find_files(archives, 2, |path| {
+        path.extension().is_some_and(|ext| ext == "deb")
+    });
+    clean_with_command("apt package cache", removals, "apt-get", &["clean"], dry_run, yes)
+}
+
+/// Downloaded RPMs kept by dnf, dnf5 and yum (with `keepcache=1` or after failed
+/// transactions), removed with `dnf clean packages`. Repository metadata is left
+/// in place.
+pub async fn cleanup_dnf(dry_run: bool, yes: bool) -> Result<u64> {
+    let roots: Vec<&Path> = ["/var/cache/dnf", "/var/cache/libdnf5", "/var/cache/yum"]
+        .into_iter()
+        .map(Path::new)
+        .filter(|root| root.exists())
+        .collect();
+    if roots.is_empty() || !system_cleaners_allowed() {
+        return Ok(0);
+    }
+
+    println!("  {} dnf/yum", style("📦").cyan());
+    let removals = roots
+        .into_iter()
+        .flat_map(|root| find_files(root, 4, |path| path.extension().is_some_and(|ext| ext == "rpm")))
+        .collect();
+    let program = if command_exists("dnf") { "dnf" } else { "yum" };
+    clean_with_command("dnf/yum package cache", removals, program, &["clean", "packages"], dry_run, yes)
+}
+
+/// Old package versions in pacman's cache, keeping the newest few of each
+/// package so a downgrade stays possible. Versions are ordered the way pacman's
+/// `vercmp` orders them, and `paccache` does the removal when it is installed.
+pub async fn cleanup_pacman(dry_run: bool, yes: bool) -> Result<u64> {
+    let cache = Path::new("/var/cache/pacman/pkg");
+    if !cache.exists() || !system_cleaners_allowed() {
+        return Ok(0);
+    }
+
+    println!("  {} pacman (keeping {} versions per package)", style("📦").cyan(), PACMAN_KEEP_VERSIONS);
+    // (name, arch) -> [(version-release, archive)]
+    let mut packages: HashMap<(String, String), Vec<(String, PathBuf)>> = HashMap::new();
+    for entry in fs::read_dir(cache).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let Some((name, arch, version)) = parse_pacman_archive(&path.file_name().unwrap_or_default().to_string_lossy()) else {
+            continue;
+        };
+        packages.entry((name, arch)).or_default().push((version, path));
+    }
+
+    let mut removals = Vec::new();
+    for ((name, _), mut versions) in packages {
+        versions.sort_by(|(a, _), (b, _)| vercmp(b, a));
+        for (_, archive) in versions.into_iter().skip(PACMAN_KEEP_VERSIONS) {
+            // Detached signatures go with their archive
+            let signature = PathBuf::from(format!("{}.sig", archive.display()));
+            for path in [archive, signature] {
+                if let Ok(metadata) = fs::metadata(&path) {
+                    removals.push(Removal {
+                        description: format!("{} {}", name, path.file_name().unwrap_or_default().to_string_lossy()),
+                        size: metadata.len(),
+                        path,
+                    });
+                }
+            }
+        }
+    }
+
+    if command_exists("paccache") {
+        let keep = PACMAN_KEEP_VERSIONS.to_string();
+        return clean_with_command("old pacman packages", removals, "paccache", &["-r", "-k", &keep], dry_run, yes);
+    }
+    clean_packages("old pacman packages", removals, dry_run, yes)
+}
+
+/// RPMs zypper kept in its package cache.
+pub async fn cleanup_zypper(dry_run: bool, yes: bool) -> Result<u64> {
+    let cache = Path::new("/var/cache/zypp/packages");
+    if !cache.exists() || !system_cleaners_allowed() {
+        return Ok(0);
+    }
+
+    println!("  {} zypper", style("📦").cyan());
+    let removals = find_files(cache, 6, |path| path.extension().is_some_and(|ext| ext == "rpm"));
+    clean_packages("zypper package cache", removals, dry_run, yes)
+}
+
+fn clean_packages(name: &str, removals: Vec<Removal>, dry_run: bool, yes: bool) -> Result<u64> {
+    if removals.is_empty() {
+        println!("    {} Nothing to clean", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    println!("    {} packages, {}", removals.len(), format_size(removals.iter().map(|r| r.size).sum()));
+    super::remove_with_confirmation(name, &removals, dry_run, yes)
+}
+
+/// Lists `removals` and, once confirmed, runs the package manager command that
+/// deletes them, so its own locking applies. Returns the bytes of the listed
+/// files that are gone afterwards.
+fn clean_with_command(
+    name: &str,
+    removals: Vec<Removal>,
+    program: &str,
+    args: &[&str],
+    dry_run: bool,
+    yes: bool,
+) -> Result<u64> {
+    if removals.is_empty() {
+        println!("    {} Nothing to clean", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    let total: u64 = removals.iter().map(|r| r.size).sum();
+    let command = format!("{} {}", program, args.join(" "));
+    println!("    {} packages, {}", removals.len(), format_size(total));
+
+    if dry_run {
+        println!("  Would run `{}` to clean {}: {}", command, name, format_size(total));
+        return Ok(total);
+    }
+
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Run `{}` to clean {}? ({})", command, name, format_size(total)))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    let output = Command::new(program).args(args).output()?;
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("`{}` failed: {}", command, String::from_utf8_lossy(&output.stderr).trim()));
+    }
+
+    Ok(removals.iter().filter(|removal| !removal.path.exists()).map(|removal| removal.size).sum())
+}
+
+fn find_files(root: &Path, max_depth: usize, matches: impl Fn(&Path) -> bool) -> Vec<Removal> {
+    WalkDir::new(root)
+        .max_depth(max_depth)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file() && matches(entry.path()))
+        .filter_map(|entry| {
+            let size = entry.metadata().ok()?.len();
+            Some(Removal {
+                description: entry.file_name().to_string_lossy().into_owned(),
+                size,
+                path: entry.into_path(),
+            })
+        })
+        .collect()
+}
+
+/// Splits `name-version-release-arch.pkg.tar.zst` into `(name, arch,
+/// version-release)`. Package names may contain dashes, so the fields are taken
+/// from the right.
+fn parse_pacman_archive(file_name: &str) -> Option<(String, String, String)> {
+    let (stem, _) = file_name.split_once(".pkg.tar")?;
+    if file_name.ends_with(".sig") || file_name.ends_with(".part") {
+        return None;
+    }
+
+    let mut fields = stem.rsplitn(4, '-');
+    let arch = fields.next()?;
+    let release = fields.next()?;
+    let version = fields.next()?;
+    let name = fields.next()?;
+    Some((name.to_string(), arch.to_string(), format!("{}-{}", version, release)))
+}
+
+/// Compares two pacman versions, `[epoch:]pkgver[-pkgrel]`, the way libalpm
+/// does: epochs first, then versions, then releases when both have one.
+fn vercmp(a: &str, b: &str) -> std::cmp::Ordering {
+    if a == b {
+        return std::cmp::Ordering::Equal;
+    }
+    let (epoch_a, version_a, release_a) = split_evr(a);
+    let (epoch_b, version_b, release_b) = split_evr(b);
+
+    rpmvercmp(epoch_a, epoch_b)
+        .then_with(|| rpmvercmp(version_a, version_b))
+        .then_with(|| match (release_a, release_b) {
+            (Some(release_a), Some(release_b)) => rpmvercmp(release_a, release_b),
+            _ => std::cmp::Ordering::Equal,
+        })
+}
+
+/// `1:2.0-3` -> (`1`, `2.0`, `Some("3")`); the epoch defaults to `0`.
+fn split_evr(evr: &str) -> (&str, &str, Option<&str>) {
+    let (epoch, rest) = match evr.split_once(':') {
+        Some((epoch, rest)) if epoch.bytes().all(|b| b.is_ascii_digit()) => (if epoch.is_empty() { "0" } else { epoch }, rest),
+        _ => ("0", evr),
+    };
+    match rest.rsplit_once('-') {
+        Some((version, release)) => (epoch, version, Some(release)),
+        None => (epoch, rest, None),
+    }
+}
+
+/// rpm's segment comparison: alternating runs of digits and letters, compared
+/// numerically or lexically, with separators only counting by their length. A
+/// trailing letter segment sorts before the end of the string (`1.0a` < `1.0`).
+fn rpmvercmp(a: &str, b: &str) -> std::cmp::Ordering {
+    use std::cmp::Ordering::{Equal, Greater, Less};
+
+    if a == b {
+        return Equal;
+    }
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() && j < b.len() {
+        let (separator_a, separator_b) = (i, j);
+        while i < a.len() && !a[i].is_ascii_alphanumeric() {
+            i += 1;
+        }
+        while j < b.len() && !b[j].is_ascii_alphanumeric() {
+            j += 1;
+        }
+        if i == a.len() || j == b.len() {
+            break;
+        }
+        if i - separator_a != j - separator_b {
+            return (i - separator_a).cmp(&(j - separator_b));
+        }
+
+        let numeric = a[i].is_ascii_digit();
+        let in_segment = |byte: &u8| if numeric { byte.is_ascii_digit() } else { byte.is_ascii_alphabetic() };
+        let (start_a, start_b) = (i, j);
+        while i < a.len() && in_segment(&a[i]) {
+            i += 1;
+        }
+        while j < b.len() && in_segment(&b[j]) {
+            j += 1;
+        }
+        // A number is newer than letters in the same position
+        if j == start_b {
+            return if numeric { Greater } else { Less };
+        }
+
+        let (mut segment_a, mut segment_b) = (&a[start_a..i], &b[start_b..j]);
+        if numeric {
+            while segment_a.first() == Some(&b'0') {
+                segment_a = &segment_a[1..];
+            }
+            while segment_b.first() == Some(&b'0') {
+                segment_b = &segment_b[1..];
+            }
+            let by_length = segment_a.len().cmp(&segment_b.len());
+            if by_length != Equal {
+                return by_length;
+            }
+        }
+        let by_content = segment_a.cmp(segment_b);
+        if by_content != Equal {
+            return by_content;
+        }
+    }
+
+    let (rest_a, rest_b) = (&a[i..], &b[j..]);
+    if rest_a.is_empty() && rest_b.is_empty() {
+        return Equal;
+    }
+    // A remaining letter segment never beats the end of the other string
+    let alpha_a = rest_a.first().is_some_and(u8::is_ascii_alphabetic);
+    let alpha_b = rest_b.first().is_some_and(u8::is_ascii_alphabetic);
+    if (rest_a.is_empty() && !alpha_b) || alpha_a {
+        Less
+    } else {
+        Greater
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering::{Equal, Greater, Less};
+
+    #[test]
+    fn vercmp_matches_pacman() {
+        let cases = [
+            ("1.5.0", "1.5.0", Equal),
+            ("1.5.1", "1.5.0", Greater),
+            ("1.5.0-1", "1.5.0-2", Less),
+            ("1.5.0", "1.5.0-2", Equal),
+            ("1.5", "1.5.1", Less),
+            ("1.5.a", "1.5", Greater),
+            ("1.5a", "1.5", Less),
+            ("1.5.b", "1.5.a", Greater),
+            ("1.5.1", "1.5.b", Greater),
+            ("1.5.b-1", "1.5.b", Equal),
+            ("1.5-1", "1.5.b", Less),
+            ("1.1", "1.1.1", Less),
+            ("1.5.0", "1:1.0", Less),
+            ("1:1.0", "2:1.0", Less),
+            ("0:1.0", "1.0", Equal),
+            ("1.001", "1.1", Equal),
+            ("1.10", "1.9", Greater),
+            ("1.0rc1", "1.0", Less),
+            ("20240101-1", "20231231-5", Greater),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(vercmp(a, b), expected, "{} vs {}", a, b);
+            assert_eq!(vercmp(b, a), expected.reverse(), "{} vs {}", b, a);
+        }
+    }
+
+    #[test]
+    fn parses_pacman_archive_names() {
+        assert_eq!(
+            parse_pacman_archive("python-pip-1:24.0-2-any.pkg.tar.zst"),
+            Some(("python-pip".to_string(), "any".to_string(), "1:24.0-2".to_string()))
+        );
+        assert_eq!(parse_pacman_archive("linux-6.8.1.arch1-1-x86_64.pkg.tar.zst.sig"), None);
+    }
+}
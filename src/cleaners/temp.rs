@@ -0,0 +1,200 @@
+use anyhow::Result;
+use console::style;
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::io::ErrorKind;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use super::Removal;
+use crate::utils::{days_ago, format_size, last_used};
+
+/// Temp files unused for this long are removed by default.
+const DEFAULT_MIN_AGE_DAYS: u64 = 7;
+
+/// Directories that belong to running services or the X server, never touched.
+const PROTECTED_DIRS: &[&str] = &[".X11-unix", ".ICE-unix", ".XIM-unix", ".font-unix", ".Test-unix"];
+
+struct TempScan {
+    uid: u32,
+    is_root: bool,
+    cutoff: SystemTime,
+    /// `(device, inode)` of every file and working directory held by a process
+    in_use: HashSet<(u64, u64)>,
+}
+
+/// Removes old files from `/tmp`, `/var/tmp` and `$TMPDIR`. Only the current
+/// user's files are considered unless running as root, and sockets, lock files
+/// and files a process has open are always kept; nothing is removed when open
+/// files cannot be listed. Directories left empty go too.
+pub async fn cleanup_temp_files(min_age_days: Option<u64>, dry_run: bool, yes: bool) -> Result<u64> {
+    let days = min_age_days.unwrap_or(DEFAULT_MIN_AGE_DAYS);
+
+    let mut temp_dirs = vec![PathBuf::from("/tmp"), PathBuf::from("/var/tmp")];
+    // On macOS $TMPDIR is a per-user directory under /var/folders
+    let tmpdir = env::temp_dir();
+    if !temp_dirs.iter().any(|dir| same_dir(dir, &tmpdir)) {
+        temp_dirs.push(tmpdir);
+    }
+    temp_dirs.retain(|dir| dir.is_dir());
+
+    let Some(in_use) = files_in_use(&temp_dirs) else {
+        println!("    {} Cannot tell which temp files are open (no /proc or lsof); not removing any",
+            style("⚠").yellow());
+        return Ok(0);
+    };
+    let scan = TempScan {
+        uid: crate::utils::effective_uid(),
+        is_root: crate::utils::is_root(),
+        cutoff: days_ago(days),
+        in_use,
+    };
+
+    let mut removals = Vec::new();
+    for dir in &temp_dirs {
+        scan_dir(dir, &scan, &mut removals);
+    }
+
+    let dirs = removals.iter().filter(|removal| removal.path.is_dir()).count();
+    println!("    {} files and {} directories unused for {} days: {}",
+        removals.len() - dirs,
+        dirs,
+        days,
+        format_size(removals.iter().map(|r| r.size).sum())
+    );
+    if !scan.is_root {
+        println!("    {}", style("Only your own files are considered; run as root to include everyone's").dim());
+    }
+
+    // Only what passed the checks is deleted: files one by one, and directories
+    // only once empty, so anything created in them since the scan stays
+    super::remove_with_confirmation_using("old temp files", &removals, dry_run, yes, |removal| {
+        let metadata = fs::symlink_metadata(&removal.path)?;
+        if metadata.is_dir() {
+            return match fs::remove_dir(&removal.path) {
+                Err(e) if e.kind() == ErrorKind::DirectoryNotEmpty => Ok(()),
+                result => result.map_err(Into::into),
+            };
+        }
+        let unchanged = metadata.is_file() && last_used(&metadata) < scan.cutoff;
+        if !unchanged || scan.in_use.contains(&(metadata.dev(), metadata.ino())) {
+            return Err(anyhow::anyhow!("changed since the scan"));
+        }
+        Ok(fs::remove_file(&removal.path)?)
+    })
+}
+
+/// Adds removable entries below `dir` to `removals`, children before their
+/// directory, and returns whether everything in `dir` was added.
+fn scan_dir(dir: &Path, scan: &TempScan, removals: &mut Vec<Removal>) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else { return false };
+    let mut everything_removable = true;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().into_owned();
+        // Metadata is read before anything below is deleted, which would reset mtimes
+        let Ok(metadata) = fs::symlink_metadata(&path) else {
+            everything_removable = false;
+            continue;
+        };
+
+        let owned = scan.is_root || metadata.uid() == scan.uid;
+        // Listing a directory refreshes its atime, so only its mtime says anything
+        let used = if metadata.is_dir() {
+            metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH)
+        } else {
+            last_used(&metadata)
+        };
+        let old = used < scan.cutoff;
+        let in_use = scan.in_use.contains(&(metadata.dev(), metadata.ino()));
+
+        if metadata.is_dir() {
+            let protected = PROTECTED_DIRS.contains(&name.as_str()) || name.starts_with("systemd-private-");
+            if protected || !owned {
+                everything_removable = false;
+                continue;
+            }
+            if scan_dir(&path, scan, removals) && old && !in_use {
+                removals.push(Removal {
+                    description: format!("{}/", path.display()),
+                    path,
+                    size: 0,
+                });
+            } else {
+                everything_removable = false;
+            }
+        } else if metadata.is_file() && owned && old && !in_use && !is_lock_file(&name) {
+            removals.push(Removal {
+                description: path.display().to_string(),
+                size: metadata.len(),
+                path,
+            });
+        } else {
+            // Sockets, FIFOs, symlinks and anything recent, foreign or busy
+            everything_removable = false;
+        }
+    }
+
+    everything_removable
+}
+
+fn is_lock_file(name: &str) -> bool {
+    name.ends_with(".lock")
+        || name.ends_with(".lck")
+        || name.ends_with(".pid")
+        || (name.starts_with(".X") && name.ends_with("-lock"))
+}
+
+/// Files open in any process we can inspect, plus process working directories,
+/// from `/proc/<pid>/fd` and `/proc/<pid>/cwd`, or from `lsof` for `dirs` where
+/// there is no `/proc` (macOS). `None` when neither is available.
+fn files_in_use(dirs: &[PathBuf]) -> Option<HashSet<(u64, u64)>> {
+    if !Path::new("/proc/self/fd").is_dir() {
+        return files_in_use_lsof(dirs);
+    }
+    let mut in_use = HashSet::new();
+
+    for process in fs::read_dir("/proc").into_iter().flatten().flatten() {
+        if !process.file_name().to_string_lossy().chars().all(|c| c.is_ascii_digit()) {
+            continue;
+        }
+
+        let fds = fs::read_dir(process.path().join("fd")).into_iter().flatten().flatten().map(|fd| fd.path());
+        for path in fds.chain([process.path().join("cwd")]) {
+            // Following the link stats the open file itself
+            if let Ok(metadata) = fs::metadata(&path) {
+                in_use.insert((metadata.dev(), metadata.ino()));
+            }
+        }
+    }
+
+    Some(in_use)
+}
+
+/// Open files and working directories below `dirs` from `lsof -Fn +D`, which
+/// prints one `n<path>` line per open file.
+fn files_in_use_lsof(dirs: &[PathBuf]) -> Option<HashSet<(u64, u64)>> {
+    let mut in_use = HashSet::new();
+    for dir in dirs {
+        // lsof also exits with 1 when nothing is open, so only a failed spawn counts
+        let output = Command::new("lsof").arg("-Fn").arg("+D").arg(dir).output().ok()?;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            let Some(path) = line.strip_prefix('n') else { continue };
+            if let Ok(metadata) = fs::metadata(path) {
+                in_use.insert((metadata.dev(), metadata.ino()));
+            }
+        }
+    }
+    Some(in_use)
+}
+
+fn same_dir(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
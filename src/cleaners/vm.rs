@@ -0,0 +1,587 @@
+use anyhow::Result;
+use console::style;
+use dialoguer::{theme::ColorfulTheme, Confirm, MultiSelect};
+use std::collections::HashSet;
+use std::env;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use super::Removal;
+use crate::utils::{calculate_directory_size, config_home, data_home, format_date, format_size, last_used, last_used_in};
+
+/// Disk image extensions VirtualBox VMs keep next to their `.vbox` file.
+const VIRTUALBOX_DISK_EXTENSIONS: &[&str] = &["vdi", "vmdk", "vhd"];
+
+/// A VM, box or emulator image, and whether anything still refers to it.
+struct VmImage {
+    description: String,
+    path: PathBuf,
+    size: u64,
+    last_used: SystemTime,
+    orphaned: bool,
+}
+
+impl VmImage {
+    fn new(description: String, path: PathBuf, orphaned: bool) -> Self {
+        let (size, last_used) = if path.is_dir() {
+            (calculate_directory_size(&path).unwrap_or(0), last_used_in(&path))
+        } else {
+            let metadata = fs::metadata(&path).ok();
+            (
+                metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+                metadata.as_ref().map(last_used).unwrap_or(SystemTime::UNIX_EPOCH),
+            )
+        };
+        VmImage { description, path, size, last_used, orphaned }
+    }
+}
+
+/// Runs the Vagrant, libvirt, VirtualBox, Android emulator, minikube and kind
+/// cleaners in turn.
+pub async fn cleanup_vm_images(dry_run: bool, yes: bool) -> Result<u64> {
+    let mut total_freed = 0u64;
+
+    total_freed += cleanup_vagrant(dry_run, yes).await?;
+    total_freed += cleanup_libvirt(dry_run, yes).await?;
+    total_freed += cleanup_virtualbox(dry_run, yes).await?;
+    total_freed += cleanup_android_emulator(dry_run, yes).await?;
+    total_freed += cleanup_minikube(dry_run, yes).await?;
+    total_freed += cleanup_kind(dry_run, yes).await?;
+
+    Ok(total_freed)
+}
+
+/// Removes Vagrant box versions that no machine in Vagrant's machine index uses.
+pub async fn cleanup_vagrant(dry_run: bool, yes: bool) -> Result<u64> {
+    let vagrant_home = match env::var("VAGRANT_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(shellexpand::tilde("~/.vagrant.d").as_ref()),
+    };
+    let boxes = vagrant_home.join("boxes");
+    if !boxes.is_dir() {
+        println!("  {} Vagrant boxes not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    // (box name, version) of every machine whose Vagrantfile still exists
+    let index = fs::read_to_string(vagrant_home.join("data/machine-index/index")).unwrap_or_default();
+    let index: serde_json::Value = serde_json::from_str(&index).unwrap_or_default();
+    let used: HashSet<(String, String)> = index["machines"]
+        .as_object()
+        .into_iter()
+        .flat_map(|machines| machines.values())
+        .filter(|machine| machine["vagrantfile_path"].as_str().is_some_and(|path| Path::new(path).exists()))
+        .filter_map(|machine| {
+            let name = machine["extra_data"]["box"]["name"].as_str()?;
+            let version = machine["extra_data"]["box"]["version"].as_str()?;
+            Some((name.to_string(), version.to_string()))
+        })
+        .collect();
+
+    let mut images = Vec::new();
+    for entry in fs::read_dir(&boxes).into_iter().flatten().flatten() {
+        // Box directories encode the slash in `owner/name`
+        let name = entry.file_name().to_string_lossy().replace("-VAGRANTSLASH-", "/");
+        for version in fs::read_dir(entry.path()).into_iter().flatten().flatten() {
+            if !version.path().is_dir() {
+                continue;
+            }
+            let version_name = version.file_name().to_string_lossy().into_owned();
+            let orphaned = !used.contains(&(name.clone(), version_name.clone()));
+            images.push(VmImage::new(format!("{} {}", name, version_name), version.path(), orphaned));
+        }
+    }
+
+    report_images("Vagrant", "unused Vagrant boxes", images, dry_run, yes)
+}
+
+/// Removes libvirt disk images that no domain uses, directly or as the backing
+/// file of a disk that is used. Domains are listed through `virsh` when it is
+/// installed, which includes transient ones, and read from the definition and
+/// runtime directories otherwise.
+pub async fn cleanup_libvirt(dry_run: bool, yes: bool) -> Result<u64> {
+    let mut image_dirs = vec![data_home().join("libvirt/images")];
+    let mut uris = vec!["qemu:///session"];
+    let mut domain_dirs = vec![config_home().join("libvirt/qemu")];
+    if let Ok(runtime) = env::var("XDG_RUNTIME_DIR") {
+        domain_dirs.push(PathBuf::from(runtime).join("libvirt/qemu/run"));
+    }
+    // The system instance's images and definitions are only readable by root
+    if crate::utils::is_root() {
+        image_dirs.push(PathBuf::from("/var/lib/libvirt/images"));
+        uris.push("qemu:///system");
+        domain_dirs.push(PathBuf::from("/etc/libvirt/qemu"));
+        domain_dirs.push(PathBuf::from("/run/libvirt/qemu"));
+    }
+    image_dirs.retain(|dir| dir.is_dir());
+    if image_dirs.is_empty() {
+        println!("  {} libvirt images not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let Some(domains) = libvirt_domain_xml(&uris, &domain_dirs) else {
+        println!("  {} Cannot list libvirt domains; not flagging any libvirt image", style("⚠").yellow());
+        return Ok(0);
+    };
+    // Disks given as storage pool volumes don't name their file, so any of the
+    // images could be one of them
+    if domains.iter().any(|xml| !xml_attributes(xml, "pool").is_empty()) {
+        println!("  {} Some libvirt domains use storage pool volumes; not flagging any libvirt image", style("ℹ").blue());
+        return Ok(0);
+    }
+    let mut referenced: Vec<PathBuf> = domains.iter().flat_map(|xml| xml_attributes(xml, "file")).map(PathBuf::from).collect();
+
+    // Follow backing chains so base images of overlays are kept
+    let mut used = HashSet::new();
+    while let Some(image) = referenced.pop() {
+        if let Some(backing) = qcow2_backing_file(&image) {
+            referenced.push(image.parent().map(|dir| dir.join(&backing)).unwrap_or(backing));
+        }
+        used.insert(fs::canonicalize(&image).unwrap_or(image));
+    }
+
+    let mut images = Vec::new();
+    for dir in &image_dirs {
+        for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+            if !entry.path().is_file() {
+                continue;
+            }
+            let orphaned = !used.contains(&fs::canonicalize(entry.path()).unwrap_or(entry.path()));
+            images.push(VmImage::new(entry.path().display().to_string(), entry.path(), orphaned));
+        }
+    }
+
+    report_images("libvirt", "unused libvirt images", images, dry_run, yes)
+}
+
+/// The XML of every domain, persistent or transient, on the `uris` libvirt
+/// connections, or in the definition and runtime directories when `virsh` is
+/// not installed. None when some of them cannot be read.
+fn libvirt_domain_xml(uris: &[&str], domain_dirs: &[PathBuf]) -> Option<Vec<String>> {
+    let virsh = |args: &[&str]| {
+        let output = Command::new("virsh").args(args).output().ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    };
+
+    if crate::utils::command_exists("virsh") {
+        let mut domains = Vec::new();
+        for uri in uris {
+            let names = virsh(&["-c", uri, "list", "--all", "--name"])?;
+            for name in names.lines().map(str::trim).filter(|name| !name.is_empty()) {
+                domains.push(virsh(&["-c", uri, "dumpxml", name])?);
+            }
+        }
+        return Some(domains);
+    }
+
+    let mut domains = Vec::new();
+    for dir in domain_dirs.iter().filter(|dir| dir.exists()) {
+        for entry in fs::read_dir(dir).ok()?.flatten() {
+            if entry.path().extension().is_some_and(|ext| ext == "xml") {
+                domains.push(fs::read_to_string(entry.path()).ok()?);
+            }
+        }
+    }
+    Some(domains)
+}
+
+/// Removes VM folders whose `.vbox` is not registered with VirtualBox, and disk
+/// images inside registered VM folders that the VM no longer attaches. Whole VM
+/// folders are only removed when asked interactively, never with `--yes`.
+pub async fn cleanup_virtualbox(dry_run: bool, yes: bool) -> Result<u64> {
+    let settings = if cfg!(target_os = "macos") {
+        PathBuf::from(shellexpand::tilde("~/Library/VirtualBox/VirtualBox.xml").as_ref())
+    } else {
+        config_home().join("VirtualBox/VirtualBox.xml")
+    };
+    let Ok(xml) = fs::read_to_string(&settings) else {
+        println!("  {} VirtualBox not found", style("ℹ").dim());
+        return Ok(0);
+    };
+
+    // Relative `src` paths are relative to the directory of VirtualBox.xml
+    let settings_dir = settings.parent().unwrap_or(Path::new("/"));
+    let registered: HashSet<PathBuf> = xml_attributes(&xml, "src")
+        .into_iter()
+        .map(|src| canonical(&settings_dir.join(src)))
+        .collect();
+    let machine_folder = xml_attributes(&xml, "defaultMachineFolder")
+        .into_iter()
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/VirtualBox VMs").as_ref()));
+
+    let mut images = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in fs::read_dir(&machine_folder).into_iter().flatten().flatten() {
+        let vm_dir = entry.path();
+        let vbox_files: Vec<PathBuf> = fs::read_dir(&vm_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "vbox"))
+            .collect();
+        let Some(vbox) = vbox_files.iter().find(|vbox| registered.contains(&canonical(vbox))) else {
+            if vbox_files.is_empty() {
+                continue;
+            }
+            if yes {
+                skipped.push(vm_dir);
+            } else {
+                images.push(VmImage::new(format!("{} (not registered)", vm_dir.display()), vm_dir, true));
+            }
+            continue;
+        };
+
+        images.push(VmImage::new(vm_dir.display().to_string(), vm_dir.clone(), false));
+
+        // Disk locations are absolute or relative to the VM folder
+        let attached: HashSet<PathBuf> = xml_attributes(&fs::read_to_string(vbox).unwrap_or_default(), "location")
+            .into_iter()
+            .map(|location| canonical(&vm_dir.join(location)))
+            .collect();
+        for dir in [vm_dir.clone(), vm_dir.join("Snapshots")] {
+            for disk in fs::read_dir(dir).into_iter().flatten().flatten().map(|entry| entry.path()) {
+                let is_disk = disk.extension().is_some_and(|ext| VIRTUALBOX_DISK_EXTENSIONS.iter().any(|d| ext == *d));
+                if is_disk && !attached.contains(&canonical(&disk)) {
+                    images.push(VmImage::new(format!("{} (detached disk)", disk.display()), disk, true));
+                }
+            }
+        }
+    }
+
+    for vm_dir in &skipped {
+        println!("  {} Skipping unregistered VM folder {} with --yes; run interactively to review it",
+            style("ℹ").blue(),
+            vm_dir.display()
+        );
+    }
+
+    report_images("VirtualBox", "unregistered VirtualBox VMs and detached disks", images, dry_run, yes)
+}
+
+/// Removes Android SDK system images no AVD is based on, and AVD directories
+/// or `.ini` files left behind without their counterpart.
+pub async fn cleanup_android_emulator(dry_run: bool, yes: bool) -> Result<u64> {
+    let sdk = android_sdk_root();
+    let avd_home = android_avd_home();
+    if !sdk.join("system-images").is_dir() && !avd_home.is_dir() {
+        println!("  {} Android SDK not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let mut images = Vec::new();
+    let mut used_sysdirs = HashSet::new();
+    let mut avd_dirs = HashSet::new();
+
+    for entry in fs::read_dir(&avd_home).into_iter().flatten().flatten() {
+        let ini = entry.path();
+        if ini.extension().is_none_or(|ext| ext != "ini") {
+            continue;
+        }
+        let contents = fs::read_to_string(&ini).unwrap_or_default();
+        let avd_dir = ini_value(&contents, "path").map(PathBuf::from).unwrap_or_else(|| ini.with_extension("avd"));
+        if !avd_dir.is_dir() {
+            images.push(VmImage::new(format!("{} (AVD directory missing)", ini.display()), ini, true));
+            continue;
+        }
+
+        let config = fs::read_to_string(avd_dir.join("config.ini")).unwrap_or_default();
+        if let Some(sysdir) = ini_value(&config, "image.sysdir.1") {
+            used_sysdirs.insert(sysdir.trim_end_matches('/').replace('\\', "/"));
+        }
+        let name = ini.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        images.push(VmImage::new(format!("AVD {}", name), avd_dir.clone(), false));
+        avd_dirs.insert(fs::canonicalize(&avd_dir).unwrap_or(avd_dir));
+    }
+
+    for entry in fs::read_dir(&avd_home).into_iter().flatten().flatten() {
+        let dir = entry.path();
+        let is_avd = dir.is_dir() && dir.extension().is_some_and(|ext| ext == "avd");
+        if is_avd && !avd_dirs.contains(&fs::canonicalize(&dir).unwrap_or(dir.clone())) {
+            images.push(VmImage::new(format!("{} (no .ini)", dir.display()), dir, true));
+        }
+    }
+
+    // system-images/<api level>/<tag>/<abi>
+    let system_images = sdk.join("system-images");
+    for api in fs::read_dir(&system_images).into_iter().flatten().flatten() {
+        for tag in fs::read_dir(api.path()).into_iter().flatten().flatten() {
+            for abi in fs::read_dir(tag.path()).into_iter().flatten().flatten() {
+                if !abi.path().is_dir() {
+                    continue;
+                }
+                let sysdir = format!("system-images/{}/{}/{}",
+                    api.file_name().to_string_lossy(),
+                    tag.file_name().to_string_lossy(),
+                    abi.file_name().to_string_lossy()
+                );
+                let orphaned = !used_sysdirs.contains(&sysdir);
+                images.push(VmImage::new(sysdir, abi.path(), orphaned));
+            }
+        }
+    }
+
+    report_images("Android emulator", "unused Android system images and AVDs", images, dry_run, yes)
+}
+
+/// Removes minikube machines without a profile, and cached preload tarballs,
+/// ISOs and kicbase images that no profile is configured to use.
+pub async fn cleanup_minikube(dry_run: bool, yes: bool) -> Result<u64> {
+    let minikube_home = match env::var("MINIKUBE_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(shellexpand::tilde("~/.minikube").as_ref()),
+    };
+    if !minikube_home.is_dir() {
+        println!("  {} minikube not found", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let mut profiles = HashSet::new();
+    // Kubernetes versions, ISO file names and kicbase tags the profiles use
+    let mut used = Vec::new();
+    for entry in fs::read_dir(minikube_home.join("profiles")).into_iter().flatten().flatten() {
+        let config = fs::read_to_string(entry.path().join("config.json")).unwrap_or_default();
+        let Ok(config) = serde_json::from_str::<serde_json::Value>(&config) else { continue };
+        profiles.insert(entry.file_name());
+        if let Some(version) = config["KubernetesConfig"]["KubernetesVersion"].as_str() {
+            used.push(format!("-{}-", version));
+        }
+        if let Some(iso) = config["MinikubeISO"].as_str().and_then(|url| url.rsplit('/').next()) {
+            used.push(iso.to_string());
+        }
+        if let Some(tag) = config["KicBaseImage"].as_str().and_then(|image| image.split(['@', ':']).nth(1)) {
+            used.push(format!("_{}", tag));
+        }
+    }
+
+    let mut images = Vec::new();
+    for entry in fs::read_dir(minikube_home.join("machines")).into_iter().flatten().flatten() {
+        if entry.path().is_dir() {
+            let orphaned = !profiles.contains(&entry.file_name());
+            images.push(VmImage::new(format!("machine {}", entry.file_name().to_string_lossy()), entry.path(), orphaned));
+        }
+    }
+
+    // The ISO and kicbase caches have one subdirectory per architecture
+    let cache = minikube_home.join("cache");
+    let mut cached = fs::read_dir(cache.join("preloaded-tarball")).into_iter().flatten().flatten().map(|e| e.path()).collect::<Vec<_>>();
+    for kind in ["iso", "kic"] {
+        for arch in fs::read_dir(cache.join(kind)).into_iter().flatten().flatten() {
+            cached.extend(fs::read_dir(arch.path()).into_iter().flatten().flatten().map(|e| e.path()));
+        }
+    }
+    for path in cached.into_iter().filter(|path| path.is_file()) {
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        let orphaned = !used.iter().any(|used| name.contains(used.as_str()));
+        images.push(VmImage::new(format!("cache {}", name), path, orphaned));
+    }
+
+    report_images("minikube", "unused minikube machines and cached images", images, dry_run, yes)
+}
+
+/// Removes `kindest/node` images that no kind cluster container runs, using
+/// `docker rmi`.
+pub async fn cleanup_kind(dry_run: bool, yes: bool) -> Result<u64> {
+    let Some(node_images) = docker_lines(&["images", "kindest/node", "--format", "{{.Repository}}:{{.Tag}}\t{{.ID}}\t{{.Size}}\t{{.CreatedSince}}"]) else {
+        println!("  {} Docker not available, skipping kind", style("ℹ").dim());
+        return Ok(0);
+    };
+    if node_images.is_empty() {
+        println!("  {} No kind node images", style("ℹ").dim());
+        return Ok(0);
+    }
+
+    let in_use = docker_lines(&["ps", "-a", "--format", "{{.Image}}"]).unwrap_or_default();
+    let mut unused = Vec::new();
+    for line in &node_images {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [image, id, size, created] = fields[..] else { continue };
+        let size = crate::scanner::parse_size_string(size).unwrap_or(0);
+        if in_use.iter().any(|used| used.starts_with(image) || used == id) {
+            println!("    {:<50} {:>12}  created {}", image, format_size(size), created);
+        } else {
+            unused.push((image.to_string(), id.to_string(), size));
+        }
+    }
+
+    if unused.is_empty() {
+        println!("  {} All kind node images are in use", style("ℹ").blue());
+        return Ok(0);
+    }
+
+    let total: u64 = unused.iter().map(|(_, _, size)| size).sum();
+    for (image, _, size) in &unused {
+        println!("    {:<50} {:>12}  {}", image, format_size(*size), style("no cluster").yellow());
+    }
+
+    if dry_run {
+        println!("  Would remove {} unused kind node images: {}", unused.len(), format_size(total));
+        return Ok(total);
+    }
+
+    if !yes && !Confirm::new()
+        .with_prompt(format!("Remove {} unused kind node images? ({})", unused.len(), format_size(total)))
+        .default(false)
+        .interact()?
+    {
+        return Ok(0);
+    }
+
+    let mut total_freed = 0u64;
+    for (image, id, size) in unused {
+        let output = Command::new("docker").args(["rmi", &id]).output()?;
+        if output.status.success() {
+            total_freed += size;
+        } else {
+            println!("  {} Could not remove {}: {}",
+                style("✗").red(),
+                image,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+    }
+
+    Ok(total_freed)
+}
+
+/// Lists the images still in use with their size and last use, then offers the
+/// orphaned ones for removal, letting the user pick which ones when asked
+/// interactively.
+fn report_images(kind: &str, name: &str, mut images: Vec<VmImage>, dry_run: bool, yes: bool) -> Result<u64> {
+    if images.is_empty() {
+        println!("  {} No {} images found", style("ℹ").dim(), kind);
+        return Ok(0);
+    }
+    images.sort_by_key(|image| std::cmp::Reverse(image.size));
+
+    let total: u64 = images.iter().map(|image| image.size).sum();
+    println!("  {} {}: {}", style("💽").cyan(), kind, format_size(total));
+    for image in images.iter().filter(|image| !image.orphaned) {
+        println!("    {:<50} {:>12}  last used {}", image.description, format_size(image.size), format_date(image.last_used));
+    }
+
+    let removals: Vec<Removal> = images
+        .into_iter()
+        .filter(|image| image.orphaned)
+        .map(|image| Removal {
+            description: format!("{} (last used {})", image.description, format_date(image.last_used)),
+            path: image.path,
+            size: image.size,
+        })
+        .collect();
+    if removals.is_empty() {
+        println!("  {} No orphaned {} images", style("✓").green(), kind);
+        return Ok(0);
+    }
+
+    if dry_run || yes {
+        return super::remove_with_confirmation(name, &removals, dry_run, yes);
+    }
+
+    let items: Vec<String> = removals
+        .iter()
+        .map(|removal| format!("{} ({})", removal.description, format_size(removal.size)))
+        .collect();
+    let selected = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Select {} to remove (space to toggle, enter to confirm)", name))
+        .items(&items)
+        .interact()?;
+
+    let mut total_freed = 0u64;
+    for removal in selected.into_iter().map(|index| &removals[index]) {
+        match crate::utils::remove_path(&removal.path) {
+            Ok(()) => total_freed += removal.size,
+            Err(e) => println!("    {} Could not remove {}: {}", style("✗").red(), removal.path.display(), e),
+        }
+    }
+
+    Ok(total_freed)
+}
+
+/// `path` with symlinks and `..` resolved, or as given if it does not exist.
+fn canonical(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// The backing file recorded in a qcow2 header, if the image has one.
+fn qcow2_backing_file(path: &Path) -> Option<PathBuf> {
+    let mut file = File::open(path).ok()?;
+    let mut header = [0u8; 20];
+    file.read_exact(&mut header).ok()?;
+    if &header[..4] != b"QFI\xfb" {
+        return None;
+    }
+
+    let offset = u64::from_be_bytes(header[8..16].try_into().ok()?);
+    let length = u32::from_be_bytes(header[16..20].try_into().ok()?) as usize;
+    if offset == 0 || length == 0 || length > 1023 {
+        return None;
+    }
+
+    let mut name = vec![0u8; length];
+    file.seek(SeekFrom::Start(offset)).ok()?;
+    file.read_exact(&mut name).ok()?;
+    Some(PathBuf::from(String::from_utf8(name).ok()?))
+}
+
+/// Every value of `name='...'` or `name="..."` in an XML document.
+fn xml_attributes(xml: &str, name: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    for quote in ['"', '\''] {
+        let prefix = format!(" {}={}", name, quote);
+        let mut rest = xml;
+        while let Some(start) = rest.find(&prefix) {
+            rest = &rest[start + prefix.len()..];
+            if let Some(end) = rest.find(quote) {
+                values.push(rest[..end].replace("&amp;", "&"));
+            }
+        }
+    }
+    values
+}
+
+/// The value of `key=value` in an Android `.ini` file.
+fn ini_value<'a>(contents: &'a str, key: &str) -> Option<&'a str> {
+    contents
+        .lines()
+        .find_map(|line| line.trim().strip_prefix(key)?.trim_start().strip_prefix('='))
+        .map(str::trim)
+}
+
+fn android_sdk_root() -> PathBuf {
+    for var in ["ANDROID_HOME", "ANDROID_SDK_ROOT"] {
+        if let Ok(dir) = env::var(var) {
+            if !dir.is_empty() {
+                return PathBuf::from(dir);
+            }
+        }
+    }
+    let default = if cfg!(target_os = "macos") { "~/Library/Android/sdk" } else { "~/Android/Sdk" };
+    PathBuf::from(shellexpand::tilde(default).as_ref())
+}
+
+fn android_avd_home() -> PathBuf {
+    if let Ok(dir) = env::var("ANDROID_AVD_HOME") {
+        if !dir.is_empty() {
+            return PathBuf::from(dir);
+        }
+    }
+    match env::var("ANDROID_USER_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir).join("avd"),
+        _ => PathBuf::from(shellexpand::tilde("~/.android/avd").as_ref()),
+    }
+}
+
+fn docker_lines(args: &[&str]) -> Option<Vec<String>> {
+    let output = Command::new("docker").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).lines().map(String::from).collect())
+}
+
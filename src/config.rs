@@ -0,0 +1,97 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::cleaners::profiles::Profile;
+use crate::cleaners::TARGETS;
+
+/// User configuration read from `config.toml`.
+///
+/// ```toml
+/// project_roots = ["~/code", "~/work"]
+///
+/// [profiles.ci-runner]
+/// description = "Keep CI disks lean"
+/// targets = ["docker", "npm", "pip"]
+/// retention = { min_age_days = 3, max_cache_size_mb = 1024 }
+/// docker = { images = true, volumes = true }
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Directories searched for projects (Cargo `target/` dirs, toolchain pins, ...)
+    pub project_roots: Vec<String>,
+    pub profiles: HashMap<String, Profile>,
+}
+
+/// Used when `project_roots` is not configured; only the ones that exist are searched.
+const DEFAULT_PROJECT_ROOTS: &[&str] = &[
+    "~/Projects", "~/projects", "~/code", "~/Code", "~/src", "~/dev",
+    "~/Developer", "~/workspace", "~/repos", "~/git",
+];
+
+impl Config {
+    /// Rejects profiles that name targets SpaceCleaner does not know, so a typo
+    /// fails when the config loads rather than halfway through a cleanup.
+    fn validate(&self) -> Result<()> {
+        let known = |target: &str| TARGETS.iter().any(|(name, _)| *name == target);
+
+        let mut names: Vec<&String> = self.profiles.keys().collect();
+        names.sort();
+        for name in names {
+            if let Some(target) = self.profiles[name].targets.iter().find(|target| !known(target)) {
+                return Err(anyhow::anyhow!(
+                    "Profile '{}' has unknown target '{}' (expected one of: {})",
+                    name,
+                    target,
+                    TARGETS.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn project_roots(&self) -> Vec<PathBuf> {
+        let roots: Vec<&str> = if self.project_roots.is_empty() {
+            DEFAULT_PROJECT_ROOTS.to_vec()
+        } else {
+            self.project_roots.iter().map(String::as_str).collect()
+        };
+
+        let mut expanded: Vec<PathBuf> = roots
+            .into_iter()
+            .filter_map(|root| fs::canonicalize(shellexpand::tilde(root).as_ref()).ok())
+            .filter(|root| root.is_dir())
+            .collect();
+
+        // ~/code and ~/Code are the same directory on case-insensitive filesystems
+        expanded.sort();
+        expanded.dedup();
+        expanded
+    }
+}
+
+/// Location of the config file, following `XDG_CONFIG_HOME`.
+pub fn config_path() -> PathBuf {
+    crate::utils::config_home().join("spacecleaner/config.toml")
+}
+
+/// Loads the config file, returning the defaults when it does not exist.
+pub fn load() -> Result<Config> {
+    let path = config_path();
+
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    let config: Config = toml::from_str(&contents)
+        .with_context(|| format!("Invalid config file {}", path.display()))?;
+    config.validate().with_context(|| format!("Invalid config file {}", path.display()))?;
+
+    Ok(config)
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+use console::style;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The most recent run of a cleanup profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupRecord {
+    pub timestamp: u64,
+    pub freed_bytes: u64,
+}
+
+fn cleanups_path() -> PathBuf {
+    crate::utils::state_dir().join("last-cleanup.json")
+}
+
+/// Last cleanup per profile name. A missing or unreadable file means no history.
+pub fn load_cleanups() -> HashMap<String, CleanupRecord> {
+    fs::read_to_string(cleanups_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Records a cleanup run, warning instead of failing when the state directory is
+/// not writable. Dry runs free nothing and are not recorded.
+pub fn record_cleanup_or_warn(name: &str, freed_bytes: u64, dry_run: bool) {
+    if dry_run {
+        return;
+    }
+    if let Err(e) = record_cleanup(name, freed_bytes) {
+        println!("  {} Could not record cleanup history: {}", style("⚠").yellow(), e);
+    }
+}
+
+pub fn record_cleanup(profile: &str, freed_bytes: u64) -> Result<()> {
+    let mut cleanups = load_cleanups();
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+
+    cleanups.insert(profile.to_string(), CleanupRecord { timestamp, freed_bytes });
+
+    let path = cleanups_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(&cleanups)?)?;
+
+    Ok(())
+}
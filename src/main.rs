@@ -1,11 +1,19 @@
 use clap::{Parser, Subcommand};
 use anyhow::Result;
 use console::style;
+use std::path::PathBuf;
+use std::time::Duration;
 
 mod scanner;
+mod snapshot;
 mod cleaners;
+mod config;
+mod history;
+mod metrics;
+mod report;
 mod ui;
 mod utils;
+mod watch;
 
 #[derive(Parser)]
 #[command(
@@ -22,12 +30,28 @@ struct Cli {
     
     #[arg(short, long, help = "Skip confirmation prompts")]
     yes: bool,
+    
+    #[arg(long, help = "Allow cleaners that touch system-wide files (Linux, requires root)")]
+    system: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Scan and show current storage usage
-    Scan,
+    Scan {
+        #[arg(long, help = "Save a snapshot of directory sizes for `diff`")]
+        save: bool,
+    },
+    /// Compare two saved scan snapshots (defaults to the two most recent)
+    Diff {
+        /// Older snapshot: a file, a saved snapshot name or `latest`
+        old: Option<String>,
+        /// Newer snapshot (defaults to `latest`)
+        new: Option<String>,
+        
+        #[arg(long, default_value_t = 15, help = "How many directories to list per direction")]
+        limit: usize,
+    },
     /// Interactive cleanup menu
     Interactive,
     /// Quick cleanup of common safe caches
@@ -36,6 +60,48 @@ enum Commands {
     Docker,
     /// Clean all cache directories
     Caches,
+    /// Sort the Downloads folder by type and age and move old files to the Trash
+    Downloads {
+        #[arg(long, default_value_t = 30, help = "Offer files downloaded more than this many days ago")]
+        older_than: u64,
+    },
+    /// Run a named cleanup profile (lists profiles when none is given)
+    Run {
+        profile: Option<String>,
+    },
+    /// Generate a visual report of disk usage and cleanup recommendations
+    Report {
+        #[arg(long, help = "Where to write the self-contained HTML report")]
+        html: PathBuf,
+    },
+    /// Write disk, cache and cleanup metrics for the Prometheus node_exporter
+    Metrics {
+        #[arg(long, help = "Textfile collector file to write, e.g. /var/lib/node_exporter/spacecleaner.prom")]
+        textfile: PathBuf,
+    },
+    /// Watch disk usage and run a cleanup when it crosses a threshold
+    Watch {
+        #[arg(long, default_value = "90%", help = "Usage that triggers a cleanup, e.g. 90%")]
+        threshold: String,
+        
+        #[arg(long, default_value = "quick", help = "Cleanup profile to run, e.g. quick, caches or docker")]
+        action: String,
+        
+        #[arg(long, default_value_t = 5.0, help = "Percentage points usage must drop below the threshold before re-arming")]
+        hysteresis: f64,
+        
+        #[arg(long, default_value_t = 60, help = "Seconds between usage checks")]
+        interval: u64,
+        
+        #[arg(long, default_value_t = 3600, help = "Minimum seconds between two cleanups")]
+        cooldown: u64,
+        
+        #[arg(long, help = "Filesystem to watch (defaults to the one holding $HOME)")]
+        path: Option<PathBuf>,
+        
+        #[arg(long, help = "Where to write the JSON status file")]
+        status_file: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
@@ -45,9 +111,21 @@ async fn main() -> Result<()> {
     println!("{}", style("🧹 SpaceCleaner - Fast Storage Cleanup Tool").bold().cyan());
     println!();
 
+    if cli.system {
+        cleaners::system::enable_system_cleaners();
+    }
+
     match &cli.command {
-        Some(Commands::Scan) => {
-            scanner::show_storage_info().await?;
+        Some(Commands::Scan { save }) => {
+            let storage = scanner::show_storage_info().await?;
+            
+            if *save {
+                let path = snapshot::save_snapshot(&storage).await?;
+                println!("\n{} Snapshot saved to {}", style("💾").green(), path.display());
+            }
+        },
+        Some(Commands::Diff { old, new, limit }) => {
+            snapshot::show_diff(old.as_deref(), new.as_deref(), *limit)?;
         },
         Some(Commands::Interactive) => {
             ui::run_interactive_mode(cli.dry_run, cli.yes).await?;
@@ -61,6 +139,44 @@ async fn main() -> Result<()> {
         Some(Commands::Caches) => {
             cleaners::caches::cleanup_all_caches(cli.dry_run, cli.yes).await?;
         },
+        Some(Commands::Downloads { older_than }) => {
+            let size = cleaners::downloads::cleanup_downloads(Some(*older_than), cli.dry_run, cli.yes).await?;
+            history::record_cleanup_or_warn("downloads", size, cli.dry_run);
+            if size > 0 {
+                let verb = if cli.dry_run { "Would move to Trash" } else { "Moved to Trash" };
+                println!("\n{} {}: {}", style("🎉").green(), verb, utils::format_size(size));
+            }
+        },
+        Some(Commands::Run { profile }) => {
+            match profile {
+                Some(name) => {
+                    cleaners::profiles::run_named_profile(name, cli.dry_run, cli.yes).await?;
+                }
+                None => cleaners::profiles::list_profiles()?,
+            }
+        },
+        Some(Commands::Report { html }) => {
+            report::write_html_report(html).await?;
+        },
+        Some(Commands::Metrics { textfile }) => {
+            metrics::export_metrics(textfile).await?;
+        },
+        Some(Commands::Watch { threshold, action, hysteresis, interval, cooldown, path, status_file }) => {
+            let threshold = watch::parse_threshold(threshold)?;
+            let options = watch::WatchOptions {
+                path: match path {
+                    Some(path) => path.clone(),
+                    None => PathBuf::from(std::env::var("HOME")?),
+                },
+                threshold,
+                hysteresis: watch::parse_hysteresis(*hysteresis, threshold)?,
+                action: action.clone(),
+                interval: Duration::from_secs((*interval).max(1)),
+                cooldown: Duration::from_secs(*cooldown),
+                status_file: status_file.clone().unwrap_or_else(watch::default_status_file),
+            };
+            watch::run_watch(options, cli.dry_run).await?;
+        },
         None => {
             // Default to interactive mode
             ui::run_interactive_mode(cli.dry_run, cli.yes).await?;
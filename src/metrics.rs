@@ -0,0 +1,100 @@
+use anyhow::Result;
+use console::style;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+/// Collects disk, cache, Docker and cleanup metrics in Prometheus exposition format
+/// and writes them for node_exporter's textfile collector.
+pub async fn export_metrics(textfile: &Path) -> Result<()> {
+    let home = env::var("HOME")?;
+    let storage = crate::scanner::get_storage_info().await?;
+    // An empty `docker system df` means the daemon is not running
+    let docker = crate::scanner::get_docker_info()
+        .await
+        .ok()
+        .filter(|docker| !docker.images.is_empty());
+    let cleanups = crate::history::load_cleanups();
+
+    let mut out = String::new();
+    let home_label = escape_label(&home);
+
+    write_metric(&mut out, "spacecleaner_filesystem_size_bytes", "gauge",
+        "Size of the filesystem holding the home directory.",
+        &[(format!("path=\"{}\"", home_label), storage.total)]);
+    write_metric(&mut out, "spacecleaner_filesystem_used_bytes", "gauge",
+        "Used bytes on the filesystem holding the home directory.",
+        &[(format!("path=\"{}\"", home_label), storage.used)]);
+    write_metric(&mut out, "spacecleaner_filesystem_available_bytes", "gauge",
+        "Available bytes on the filesystem holding the home directory.",
+        &[(format!("path=\"{}\"", home_label), storage.available)]);
+
+    let mut caches: Vec<_> = storage.cache_breakdown.iter().collect();
+    caches.sort();
+    write_metric(&mut out, "spacecleaner_cache_size_bytes", "gauge",
+        "Size of each scanned cache directory.",
+        &caches
+            .iter()
+            .map(|(name, size)| (format!("cache=\"{}\"", escape_label(name)), **size))
+            .collect::<Vec<_>>());
+
+    if let Some(docker) = docker {
+        write_metric(&mut out, "spacecleaner_docker_images_bytes", "gauge",
+            "Total size of Docker images.",
+            &[(String::new(), docker.total_size)]);
+        let reclaimable = crate::cleaners::docker::reclaimable_space().unwrap_or_default();
+        write_metric(&mut out, "spacecleaner_docker_reclaimable_bytes", "gauge",
+            "Bytes a Docker prune would reclaim from images, containers, volumes and build cache.",
+            &reclaimable
+                .iter()
+                .map(|(kind, size)| (format!("type=\"{}\"", escape_label(&kind.to_lowercase().replace(' ', "_"))), *size))
+                .collect::<Vec<_>>());
+    }
+
+    let mut cleanups: Vec<_> = cleanups.iter().collect();
+    cleanups.sort_by(|a, b| a.0.cmp(b.0));
+    write_metric(&mut out, "spacecleaner_last_cleanup_freed_bytes", "gauge",
+        "Bytes freed by the last run of each cleanup profile.",
+        &cleanups
+            .iter()
+            .map(|(profile, record)| (format!("profile=\"{}\"", escape_label(profile)), record.freed_bytes))
+            .collect::<Vec<_>>());
+    write_metric(&mut out, "spacecleaner_last_cleanup_timestamp_seconds", "gauge",
+        "Unix time of the last run of each cleanup profile.",
+        &cleanups
+            .iter()
+            .map(|(profile, record)| (format!("profile=\"{}\"", escape_label(profile)), record.timestamp))
+            .collect::<Vec<_>>());
+
+    // node_exporter may read at any time, so never expose a partial file
+    let tmp_path = textfile.with_extension("prom.tmp");
+    fs::write(&tmp_path, &out)?;
+    fs::rename(&tmp_path, textfile)?;
+    println!("\n{} Metrics written to {}", style("✓").green(), textfile.display());
+
+    Ok(())
+}
+
+fn write_metric(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(String, u64)]) {
+    if samples.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for (labels, value) in samples {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{} {}", name, value);
+        } else {
+            let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
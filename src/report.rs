@@ -0,0 +1,347 @@
+use anyhow::Result;
+use chrono::Local;
+use console::style;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use crate::scanner::{DockerInfo, StorageInfo};
+use crate::utils::format_size;
+
+/// Treemap size in CSS pixels; tiles are laid out in these units so they keep
+/// the aspect ratios the layout chose.
+const TREEMAP_WIDTH: f64 = 1000.0;
+const TREEMAP_HEIGHT: f64 = 420.0;
+
+/// Colors cycled through for treemap tiles.
+const PALETTE: &[&str] = &[
+    "#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f",
+    "#edc948", "#b07aa1", "#ff9da7", "#9c755f", "#bab0ac",
+];
+
+struct Tile {
+    label: String,
+    size: u64,
+    color: &'static str,
+}
+
+struct Rect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+struct Recommendation {
+    title: String,
+    command: String,
+    savings: u64,
+}
+
+/// Writes a self-contained HTML page with the same data `scan` prints.
+pub async fn write_html_report(path: &Path) -> Result<()> {
+    println!("{}", style("📄 Building HTML Report...").bold().yellow());
+
+    let storage = crate::scanner::get_storage_info().await?;
+    let docker = crate::scanner::get_docker_info()
+        .await
+        .ok()
+        .filter(|docker| !docker.images.is_empty());
+
+    let html = render(&storage, docker.as_ref());
+    fs::write(path, html)?;
+
+    println!("\n{} Report written to {}", style("✓").green(), path.display());
+
+    Ok(())
+}
+
+fn render(storage: &StorageInfo, docker: Option<&DockerInfo>) -> String {
+    let mut caches: Vec<_> = storage.cache_breakdown.iter().collect();
+    caches.sort_by(|a, b| b.1.cmp(a.1));
+
+    let mut html = String::new();
+    let _ = write!(html, r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>SpaceCleaner Report</title>
+<style>
+body {{ font-family: -apple-system, "Segoe UI", Roboto, sans-serif; margin: 2rem auto; max-width: 1000px; color: #222; }}
+h1 {{ margin-bottom: 0; }}
+.muted {{ color: #777; }}
+.cards {{ display: flex; gap: 1rem; margin: 1.5rem 0; }}
+.card {{ flex: 1; background: #f4f6f8; border-radius: 8px; padding: 1rem; }}
+.card .value {{ font-size: 1.6rem; font-weight: 600; }}
+.bar {{ height: 14px; background: #dde3e8; border-radius: 7px; overflow: hidden; }}
+.bar div {{ height: 100%; background: #e15759; }}
+.treemap {{ position: relative; width: 100%; aspect-ratio: 1000 / 420; border-radius: 8px; overflow: hidden; }}
+.tile {{ position: absolute; box-sizing: border-box; border: 1px solid #fff; color: #fff; font-size: 12px; padding: 4px; overflow: hidden; }}
+table {{ width: 100%; border-collapse: collapse; margin: 1rem 0; }}
+th, td {{ text-align: left; padding: 6px 8px; border-bottom: 1px solid #e3e3e3; }}
+td.size {{ text-align: right; font-variant-numeric: tabular-nums; }}
+code {{ background: #f4f6f8; padding: 2px 4px; border-radius: 4px; }}
+</style>
+</head>
+<body>
+<h1>🧹 SpaceCleaner Report</h1>
+<p class="muted">Generated {}</p>
+"#, Local::now().format("%Y-%m-%d %H:%M"));
+
+    // Disk usage summary
+    let used_percent = storage.used * 100 / storage.total.max(1);
+    let _ = write!(html, r#"<div class="cards">
+<div class="card"><div class="muted">Total</div><div class="value">{}</div></div>
+<div class="card"><div class="muted">Used</div><div class="value">{} ({}%)</div></div>
+<div class="card"><div class="muted">Available</div><div class="value">{}</div></div>
+</div>
+<div class="bar"><div style="width: {}%"></div></div>
+"#,
+        format_size(storage.total),
+        format_size(storage.used),
+        used_percent,
+        format_size(storage.available),
+        used_percent
+    );
+
+    // Treemap of the disk: scanned caches, the rest of the used space and free space.
+    // Directories inside another scanned one are already part of its tile.
+    let top_level: Vec<_> = caches
+        .iter()
+        .filter(|(name, _)| !caches.iter().any(|(parent, _)| name.starts_with(&format!("{}/", parent))))
+        .collect();
+    let scanned: u64 = top_level.iter().map(|(_, size)| **size).sum();
+    let mut tiles: Vec<Tile> = top_level
+        .iter()
+        .enumerate()
+        .map(|(i, (name, size))| Tile {
+            label: name.to_string(),
+            size: **size,
+            color: PALETTE[i % PALETTE.len()],
+        })
+        .collect();
+    tiles.push(Tile {
+        label: "Other used space".to_string(),
+        size: storage.used.saturating_sub(scanned),
+        color: "#8d99a6",
+    });
+    tiles.push(Tile {
+        label: "Free".to_string(),
+        size: storage.available,
+        color: "#c9d3dc",
+    });
+    tiles.retain(|tile| tile.size > 0);
+    tiles.sort_by_key(|tile| std::cmp::Reverse(tile.size));
+
+    html.push_str("<h2>Disk Usage Treemap</h2>\n<div class=\"treemap\">\n");
+    let sizes: Vec<f64> = tiles.iter().map(|tile| tile.size as f64).collect();
+    let bounds = Rect { x: 0.0, y: 0.0, w: TREEMAP_WIDTH, h: TREEMAP_HEIGHT };
+    for (tile, rect) in tiles.iter().zip(squarify(&sizes, bounds)) {
+        // Percentages of the pixel layout, so the treemap scales with the page
+        let _ = writeln!(html,
+            r#"<div class="tile" style="left:{:.3}%;top:{:.3}%;width:{:.3}%;height:{:.3}%;background:{}" title="{} ({})">{}<br>{}</div>"#,
+            rect.x * 100.0 / TREEMAP_WIDTH,
+            rect.y * 100.0 / TREEMAP_HEIGHT,
+            rect.w * 100.0 / TREEMAP_WIDTH,
+            rect.h * 100.0 / TREEMAP_HEIGHT,
+            tile.color,
+            escape_html(&tile.label), format_size(tile.size),
+            escape_html(&tile.label), format_size(tile.size)
+        );
+    }
+    html.push_str("</div>\n");
+
+    // Cache breakdown table
+    html.push_str("<h2>Cache Directory Sizes</h2>\n<table>\n<tr><th>Directory</th><th class=\"size\">Size</th></tr>\n");
+    for (name, size) in &caches {
+        let _ = writeln!(html, "<tr><td>{}</td><td class=\"size\">{}</td></tr>",
+            escape_html(name), format_size(**size));
+    }
+    if caches.is_empty() {
+        html.push_str("<tr><td colspan=\"2\" class=\"muted\">No cache directories found</td></tr>\n");
+    }
+    html.push_str("</table>\n");
+
+    // Docker usage
+    if let Some(docker) = docker {
+        let _ = write!(html, r#"<h2>Docker Usage</h2>
+<table>
+<tr><td>Images</td><td class="size">{}</td></tr>
+<tr><td>Containers</td><td class="size">{}</td></tr>
+<tr><td>Total Size</td><td class="size">{}</td></tr>
+<tr><td>Reclaimable</td><td class="size">{}</td></tr>
+</table>
+"#,
+            escape_html(&docker.images),
+            escape_html(&docker.containers),
+            format_size(docker.total_size),
+            format_size(docker.reclaimable_size)
+        );
+    }
+
+    // Recommendations
+    let recommendations = recommendations(storage, docker);
+    let total_savings: u64 = recommendations.iter().map(|r| r.savings).sum();
+    let _ = writeln!(html, "<h2>Recommendations</h2>\n<p>Estimated savings: <strong>{}</strong></p>",
+        format_size(total_savings));
+    html.push_str("<table>\n<tr><th>What</th><th>How</th><th class=\"size\">Estimated savings</th></tr>\n");
+    for recommendation in &recommendations {
+        let _ = writeln!(html, "<tr><td>{}</td><td><code>{}</code></td><td class=\"size\">{}</td></tr>",
+            escape_html(&recommendation.title),
+            escape_html(&recommendation.command),
+            format_size(recommendation.savings));
+    }
+    if recommendations.is_empty() {
+        html.push_str("<tr><td colspan=\"3\" class=\"muted\">Nothing worth cleaning right now</td></tr>\n");
+    }
+    html.push_str("</table>\n</body>\n</html>\n");
+
+    html
+}
+
+fn recommendations(storage: &StorageInfo, docker: Option<&DockerInfo>) -> Vec<Recommendation> {
+    let mut recommendations = Vec::new();
+    let cache = |name: &str| storage.cache_breakdown.get(name).copied().unwrap_or(0);
+
+    let quick: u64 = ["Caches/Homebrew", "Caches/pip", "Caches/composer", "Caches/node-gyp", ".npm"]
+        .iter()
+        .map(|name| cache(name))
+        .sum();
+    if quick > 0 {
+        recommendations.push(Recommendation {
+            title: "Package manager caches (Homebrew, pip, npm, Composer, node-gyp)".to_string(),
+            command: "spacecleaner quick".to_string(),
+            savings: quick,
+        });
+    }
+
+    // Other app caches under ~/Library/Caches and ~/.cache are regenerated on demand
+    let app_caches: u64 = storage
+        .cache_breakdown
+        .iter()
+        .filter(|(name, _)| {
+            (name.starts_with("Caches/") || name.as_str() == ".cache")
+                && !["Caches/Homebrew", "Caches/pip", "Caches/composer", "Caches/node-gyp"].contains(&name.as_str())
+        })
+        .map(|(_, size)| *size)
+        .sum();
+    if app_caches > 100 * 1024 * 1024 {
+        recommendations.push(Recommendation {
+            title: "Application caches".to_string(),
+            command: "spacecleaner interactive (Select specific caches)".to_string(),
+            savings: app_caches,
+        });
+    }
+
+    if let Some(docker) = docker {
+        if docker.reclaimable_size > 0 {
+            recommendations.push(Recommendation {
+                title: "Unused Docker images".to_string(),
+                command: "spacecleaner docker".to_string(),
+                savings: docker.reclaimable_size,
+            });
+        }
+    }
+
+    // Only what `downloads --yes` would trash: installers, extracted archives and partial downloads
+    let downloads: u64 = crate::cleaners::downloads::triage(&crate::cleaners::downloads::downloads_dir())
+        .iter()
+        .filter(|download| download.category.unattended())
+        .map(|download| download.size)
+        .sum();
+    if downloads > 100 * 1024 * 1024 {
+        recommendations.push(Recommendation {
+            title: "Installers and extracted archives in Downloads".to_string(),
+            command: "spacecleaner downloads".to_string(),
+            savings: downloads,
+        });
+    }
+
+    let dev_caches = cache(".cargo") + cache(".gradle");
+    if dev_caches > 1024 * 1024 * 1024 {
+        recommendations.push(Recommendation {
+            title: "Development caches (Cargo, Gradle)".to_string(),
+            command: "spacecleaner interactive (Advanced cleanup menu)".to_string(),
+            savings: dev_caches,
+        });
+    }
+
+    recommendations.sort_by_key(|r| std::cmp::Reverse(r.savings));
+    recommendations
+}
+
+/// Squarified treemap layout (Bruls et al.). `sizes` must be sorted largest first;
+/// the returned rectangles are in the same order and in the units of `bounds`.
+fn squarify(sizes: &[f64], bounds: Rect) -> Vec<Rect> {
+    let total: f64 = sizes.iter().sum();
+    if sizes.is_empty() || total <= 0.0 {
+        return Vec::new();
+    }
+
+    // Scale sizes to areas of the bounding rectangle
+    let scale = bounds.w * bounds.h / total;
+    let areas: Vec<f64> = sizes.iter().map(|size| size * scale).collect();
+
+    let mut rects = Vec::with_capacity(areas.len());
+    let mut free = bounds;
+    let mut start = 0;
+
+    while start < areas.len() {
+        let side = free.w.min(free.h);
+        let mut end = start + 1;
+
+        // Grow the row while it improves the worst aspect ratio
+        while end < areas.len()
+            && worst_ratio(&areas[start..end + 1], side) <= worst_ratio(&areas[start..end], side)
+        {
+            end += 1;
+        }
+
+        let row = &areas[start..end];
+        let row_area: f64 = row.iter().sum();
+
+        if free.w >= free.h {
+            // Lay the row out as a column on the left
+            let width = row_area / free.h;
+            let mut y = free.y;
+            for area in row {
+                let height = area / width;
+                rects.push(Rect { x: free.x, y, w: width, h: height });
+                y += height;
+            }
+            free = Rect { x: free.x + width, y: free.y, w: free.w - width, h: free.h };
+        } else {
+            // Lay the row out along the top
+            let height = row_area / free.w;
+            let mut x = free.x;
+            for area in row {
+                let width = area / height;
+                rects.push(Rect { x, y: free.y, w: width, h: height });
+                x += width;
+            }
+            free = Rect { x: free.x, y: free.y + height, w: free.w, h: free.h - height };
+        }
+
+        start = end;
+    }
+
+    rects
+}
+
+fn worst_ratio(row: &[f64], side: f64) -> f64 {
+    let sum: f64 = row.iter().sum();
+    let max = row.iter().cloned().fold(f64::MIN, f64::max);
+    let min = row.iter().cloned().fold(f64::MAX, f64::min);
+    let side_sq = side * side;
+    let sum_sq = sum * sum;
+
+    (side_sq * max / sum_sq).max(sum_sq / (side_sq * min))
+}
+
+fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
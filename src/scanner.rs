@@ -5,11 +5,13 @@ use indicatif::{ProgressBar, ProgressStyle};
 use std::collections::HashMap;
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tokio::task;
 use walkdir::WalkDir;
 
+use crate::utils::calculate_directory_size;
+
 pub struct StorageInfo {
     pub total: u64,
     pub used: u64,
@@ -17,7 +19,7 @@ pub struct StorageInfo {
     pub cache_breakdown: HashMap<String, u64>,
 }
 
-pub async fn show_storage_info() -> Result<()> {
+pub async fn show_storage_info() -> Result<StorageInfo> {
     println!("{}", style("📊 Analyzing Storage Usage...").bold().yellow());
     
     // Get overall disk usage
@@ -61,7 +63,96 @@ pub async fn show_storage_info() -> Result<()> {
         }
     }
     
-    Ok(())
+    show_downloads();
+    show_app_package_leftovers();
+    show_git_repositories();
+    show_crash_dumps();
+    
+    Ok(storage)
+}
+
+/// Core dumps and crash reports, which can be several GB each.
+fn show_crash_dumps() {
+    let Ok(config) = crate::config::load() else { return };
+    let dumps = crate::cleaners::crashes::find_crash_dumps(&config.project_roots());
+    if dumps.is_empty() {
+        return;
+    }
+    
+    println!("\n{}", style("💥 Core Dumps and Crash Reports:").bold().white());
+    println!("  {} files, {}",
+        dumps.len(),
+        format_size(dumps.iter().map(|dump| dump.size).sum())
+    );
+    for dump in dumps.iter().take(5) {
+        println!("  {:<30} {:<18} {:>12}  {}",
+            style(&dump.binary).cyan(),
+            crate::utils::format_date(dump.date),
+            format_size(dump.size),
+            style(dump.kind).dim()
+        );
+    }
+}
+
+/// The largest `.git` directories under the project roots and what is taking
+/// space inside them.
+fn show_git_repositories() {
+    let Ok(config) = crate::config::load() else { return };
+    let mut repos = crate::cleaners::git::find_repos(&config.project_roots());
+    if repos.is_empty() {
+        return;
+    }
+    repos.sort_by_key(|repo| std::cmp::Reverse(repo.size));
+    
+    println!("\n{}", style("🌿 Git Repositories:").bold().white());
+    println!("  {} repositories, {} in .git",
+        repos.len(),
+        format_size(repos.iter().map(|repo| repo.size).sum())
+    );
+    for repo in repos.iter().take(5) {
+        println!("  {:<40} {:>10}  loose {}, packs {}, LFS {}  {}",
+            style(repo.path.display()).cyan(),
+            format_size(repo.size),
+            format_size(repo.loose_size),
+            format_size(repo.pack_size),
+            format_size(repo.lfs_size),
+            style(crate::cleaners::git::notes(repo)).yellow()
+        );
+    }
+}
+
+/// The Downloads folder by file type and age, shown apart from the caches.
+fn show_downloads() {
+    let downloads = crate::cleaners::downloads::triage(&crate::cleaners::downloads::downloads_dir());
+    if downloads.is_empty() {
+        return;
+    }
+    
+    println!("\n{}", style("📥 Downloads:").bold().white());
+    crate::cleaners::downloads::print_summary(&downloads);
+}
+
+/// Disabled snap revisions and unused Flatpak runtimes, when either tool is installed.
+fn show_app_package_leftovers() {
+    let snaps = crate::cleaners::snap::disabled_revisions();
+    let runtimes = crate::cleaners::flatpak::unused_runtimes();
+    if snaps.is_none() && runtimes.is_none() {
+        return;
+    }
+    
+    println!("\n{}", style("📦 Snap and Flatpak Leftovers:").bold().white());
+    if let Some(snaps) = snaps {
+        println!("  Disabled snap revisions: {} ({})",
+            snaps.len(),
+            format_size(snaps.iter().map(|revision| revision.size).sum())
+        );
+    }
+    if let Some(runtimes) = runtimes {
+        println!("  Unused Flatpak runtimes: {} (up to {})",
+            runtimes.len(),
+            format_size(runtimes.iter().map(|runtime| runtime.size).sum())
+        );
+    }
 }
 
 pub async fn get_storage_info() -> Result<StorageInfo> {
@@ -69,13 +160,7 @@ pub async fn get_storage_info() -> Result<StorageInfo> {
     let home_path = Path::new(&home);
     
     // Get disk usage using df command
-    let output = Command::new("df")
-        .arg("-h")
-        .arg(&home)
-        .output()?;
-    
-    let df_output = String::from_utf8(output.stdout)?;
-    let (total, used, available) = parse_df_output(&df_output)?;
+    let (total, used, available) = get_filesystem_usage(home_path)?;
     
     // Scan cache directories
     let cache_breakdown = scan_cache_directories(home_path).await?;
@@ -88,19 +173,39 @@ pub async fn get_storage_info() -> Result<StorageInfo> {
     })
 }
 
-async fn scan_cache_directories(home: &Path) -> Result<HashMap<String, u64>> {
-    let mut cache_breakdown = HashMap::new();
+/// Returns `(total, used, available)` bytes for the filesystem containing `path`.
+pub fn get_filesystem_usage(path: &Path) -> Result<(u64, u64, u64)> {
+    // POSIX output in 1K blocks parses the same on macOS and Linux
+    let output = Command::new("df")
+        .arg("-kP")
+        .arg(path)
+        .output()?;
     
+    let df_output = String::from_utf8(output.stdout)?;
+    parse_df_output(&df_output)
+}
+
+/// Directories under `home` that the scan reports on, with their display names.
+pub fn cache_directories(home: &Path) -> Vec<(PathBuf, &'static str)> {
     // macOS cache directories
-    let cache_dirs = vec![
+    vec![
         (home.join("Library/Caches"), "Library/Caches"),
         (home.join(".cache"), ".cache"), // Linux
-        (home.join("Downloads"), "Downloads"),
         (home.join(".npm"), ".npm"),
         (home.join(".cargo"), ".cargo"),
         (home.join(".gradle"), ".gradle"),
         (home.join("node_modules"), "node_modules"),
-    ];
+        (home.join(".local/share/Trash"), ".local/share/Trash"), // Linux desktops
+        (home.join(".terraform.d/plugin-cache"), ".terraform.d/plugin-cache"), // Infrastructure tooling
+        (home.join(".kube/cache"), ".kube/cache"),
+        (home.join(".ccache"), ".ccache"),
+    ]
+}
+
+async fn scan_cache_directories(home: &Path) -> Result<HashMap<String, u64>> {
+    let mut cache_breakdown = HashMap::new();
+    
+    let cache_dirs = cache_directories(home);
     
     let pb = ProgressBar::new(cache_dirs.len() as u64);
     pb.set_style(ProgressStyle::default_bar()
@@ -121,7 +226,7 @@ async fn scan_cache_directories(home: &Path) -> Result<HashMap<String, u64>> {
                 if name == "Library/Caches" && path.exists() {
                     if let Ok(entries) = fs::read_dir(&path) {
                         for entry in entries.flatten() {
-                            if entry.file_type().map_or(false, |t| t.is_dir()) {
+                            if entry.file_type().is_ok_and(|t| t.is_dir()) {
                                 let subdir_size = task::spawn_blocking({
                                     let subdir_path = entry.path();
                                     move || calculate_directory_size(&subdir_path)
@@ -152,18 +257,28 @@ async fn scan_cache_directories(home: &Path) -> Result<HashMap<String, u64>> {
     Ok(cache_breakdown)
 }
 
-fn calculate_directory_size(path: &Path) -> Result<u64> {
-    let mut total_size = 0;
+/// Totals for every directory below `root` up to `max_depth` levels deep,
+/// keyed by path. Deeper files still count towards their ancestors.
+pub fn calculate_directory_sizes(root: &Path, max_depth: usize) -> HashMap<PathBuf, u64> {
+    let mut sizes: HashMap<PathBuf, u64> = HashMap::new();
     
-    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+    for entry in WalkDir::new(root).into_iter().filter_map(|e| e.ok()) {
         if let Ok(metadata) = entry.metadata() {
             if metadata.is_file() {
-                total_size += metadata.len();
+                for ancestor in entry.path().ancestors().skip(1) {
+                    let depth = match ancestor.strip_prefix(root) {
+                        Ok(relative) => relative.components().count(),
+                        Err(_) => break,
+                    };
+                    if depth <= max_depth {
+                        *sizes.entry(ancestor.to_path_buf()).or_insert(0) += metadata.len();
+                    }
+                }
             }
         }
     }
     
-    Ok(total_size)
+    sizes
 }
 
 #[derive(Debug)]
@@ -174,9 +289,9 @@ pub struct DockerInfo {
     pub reclaimable_size: u64,
 }
 
-async fn get_docker_info() -> Result<DockerInfo> {
+pub async fn get_docker_info() -> Result<DockerInfo> {
     let output = Command::new("docker")
-        .args(&["system", "df"])
+        .args(["system", "df"])
         .output()?;
     
     let output_str = String::from_utf8(output.stdout)?;
@@ -225,14 +340,14 @@ fn parse_df_output(output: &str) -> Result<(u64, u64, u64)> {
         return Err(anyhow::anyhow!("Invalid df output format"));
     }
     
-    let total = parse_size_string(parts[1])?;
-    let used = parse_size_string(parts[2])?;
-    let available = parse_size_string(parts[3])?;
+    let total = parse_size_string(parts[1])? * 1024;
+    let used = parse_size_string(parts[2])? * 1024;
+    let available = parse_size_string(parts[3])? * 1024;
     
     Ok((total, used, available))
 }
 
-fn parse_size_string(size_str: &str) -> Result<u64> {
+pub fn parse_size_string(size_str: &str) -> Result<u64> {
     let size_str = size_str.trim();
     
     if size_str.ends_with("GB") || size_str.ends_with("Gi") {
@@ -241,8 +356,8 @@ fn parse_size_string(size_str: &str) -> Result<u64> {
     } else if size_str.ends_with("MB") || size_str.ends_with("Mi") {
         let num: f64 = size_str.trim_end_matches("MB").trim_end_matches("Mi").parse()?;
         Ok((num * 1024.0 * 1024.0) as u64)
-    } else if size_str.ends_with("KB") || size_str.ends_with("Ki") {
-        let num: f64 = size_str.trim_end_matches("KB").trim_end_matches("Ki").parse()?;
+    } else if size_str.ends_with("KB") || size_str.ends_with("kB") || size_str.ends_with("Ki") {
+        let num: f64 = size_str.trim_end_matches("KB").trim_end_matches("kB").trim_end_matches("Ki").parse()?;
         Ok((num * 1024.0) as u64)
     } else if size_str.ends_with("B") {
         let num: u64 = size_str.trim_end_matches("B").parse()?;
@@ -0,0 +1,202 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use console::style;
+use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::task;
+
+use crate::scanner::StorageInfo;
+
+/// How many levels below each scanned directory get their own total.
+const SNAPSHOT_DEPTH: usize = 3;
+
+/// Directory sizes at one point in time, as saved by `scan --save`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub created: DateTime<Local>,
+    pub total: u64,
+    pub used: u64,
+    pub available: u64,
+    pub cache_breakdown: BTreeMap<String, u64>,
+    /// Home-relative directory paths and their total size.
+    pub directories: BTreeMap<String, u64>,
+}
+
+pub fn snapshots_dir() -> PathBuf {
+    crate::utils::state_dir().join("snapshots")
+}
+
+/// Records the directory sizes behind `storage` so they can be diffed later.
+pub async fn save_snapshot(storage: &StorageInfo) -> Result<PathBuf> {
+    let home = PathBuf::from(env::var("HOME")?);
+    let roots = crate::scanner::cache_directories(&home);
+    let mut directories = BTreeMap::new();
+
+    let pb = ProgressBar::new(roots.len() as u64);
+    pb.set_style(ProgressStyle::default_bar()
+        .template("{spinner:.green} [{elapsed_precise}] {bar:40.cyan/blue} {pos:>7}/{len:7} {msg}")
+        .unwrap());
+
+    for (path, name) in roots {
+        pb.set_message(format!("Recording {}", name));
+
+        if path.exists() {
+            let sizes = task::spawn_blocking({
+                let path = path.clone();
+                move || crate::scanner::calculate_directory_sizes(&path, SNAPSHOT_DEPTH)
+            }).await?;
+
+            // Small directories are recorded too; leaving them out would make a
+            // directory that shrank look deleted in the diff
+            for (dir, size) in sizes {
+                if let Ok(relative) = dir.strip_prefix(&home) {
+                    directories.insert(relative.to_string_lossy().into_owned(), size);
+                }
+            }
+        }
+
+        pb.inc(1);
+    }
+
+    pb.finish_with_message("Snapshot recorded");
+
+    let snapshot = Snapshot {
+        created: Local::now(),
+        total: storage.total,
+        used: storage.used,
+        available: storage.available,
+        cache_breakdown: storage.cache_breakdown.clone().into_iter().collect(),
+        directories,
+    };
+
+    let dir = snapshots_dir();
+    fs::create_dir_all(&dir)?;
+    // Milliseconds keep names sortable; the counter covers saves within the same one
+    let name = snapshot.created.format("%Y%m%d-%H%M%S-%3f").to_string();
+    let mut path = dir.join(format!("{}.json", name));
+    let mut counter = 1;
+    while path.exists() {
+        path = dir.join(format!("{}-{}.json", name, counter));
+        counter += 1;
+    }
+    fs::write(&path, serde_json::to_string(&snapshot)?)?;
+
+    Ok(path)
+}
+
+/// Saved snapshots, oldest first.
+fn list_snapshots() -> Result<Vec<PathBuf>> {
+    let dir = snapshots_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots: Vec<_> = fs::read_dir(&dir)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    // By stem, so `…-000` sorts before a same-millisecond `…-000-1`
+    snapshots.sort_by(|a, b| a.file_stem().cmp(&b.file_stem()));
+
+    Ok(snapshots)
+}
+
+/// Resolves a snapshot argument: a file path, a saved snapshot name such as
+/// `20240101-120000-000`, or `latest`.
+fn resolve_snapshot(name: &str) -> Result<PathBuf> {
+    let path = Path::new(name);
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+
+    let snapshots = list_snapshots()?;
+    if name == "latest" {
+        return snapshots.last().cloned().ok_or_else(|| anyhow::anyhow!("No snapshots saved yet"));
+    }
+
+    let saved = snapshots_dir().join(format!("{}.json", name.trim_end_matches(".json")));
+    if saved.is_file() {
+        return Ok(saved);
+    }
+
+    Err(anyhow::anyhow!("Snapshot '{}' not found in {}", name, snapshots_dir().display()))
+}
+
+fn load_snapshot(path: &Path) -> Result<Snapshot> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    serde_json::from_str(&contents).with_context(|| format!("Invalid snapshot {}", path.display()))
+}
+
+/// Shows the directories that grew or shrank the most between two snapshots.
+/// Without arguments the two most recent snapshots are compared.
+pub fn show_diff(old: Option<&str>, new: Option<&str>, limit: usize) -> Result<()> {
+    let (old_path, new_path) = match (old, new) {
+        (Some(old), Some(new)) => (resolve_snapshot(old)?, resolve_snapshot(new)?),
+        (Some(old), None) => (resolve_snapshot(old)?, resolve_snapshot("latest")?),
+        _ => {
+            let snapshots = list_snapshots()?;
+            if snapshots.len() < 2 {
+                println!("{} Need at least two snapshots to compare. Run `spacecleaner scan --save` first.",
+                    style("ℹ").blue());
+                return Ok(());
+            }
+            (snapshots[snapshots.len() - 2].clone(), snapshots[snapshots.len() - 1].clone())
+        }
+    };
+
+    let old = load_snapshot(&old_path)?;
+    let new = load_snapshot(&new_path)?;
+
+    println!("{}", style("📈 Storage Changes").bold().yellow());
+    println!("  From: {}", old.created.format("%Y-%m-%d %H:%M"));
+    println!("  To:   {}", new.created.format("%Y-%m-%d %H:%M"));
+    println!("  Disk used: {} -> {} ({})",
+        crate::utils::format_size(old.used),
+        crate::utils::format_size(new.used),
+        format_delta(new.used as i64 - old.used as i64)
+    );
+
+    let paths: BTreeSet<_> = old.directories.keys().chain(new.directories.keys()).collect();
+    let mut changes: Vec<(&String, i64)> = paths
+        .into_iter()
+        .map(|path| {
+            let before = old.directories.get(path).copied().unwrap_or(0) as i64;
+            let after = new.directories.get(path).copied().unwrap_or(0) as i64;
+            (path, after - before)
+        })
+        .filter(|(_, delta)| *delta != 0)
+        .collect();
+
+    changes.sort_by_key(|(_, delta)| std::cmp::Reverse(*delta));
+    let growth: Vec<_> = changes.iter().filter(|(_, delta)| *delta > 0).take(limit).collect();
+    let shrinkage: Vec<_> = changes.iter().rev().filter(|(_, delta)| *delta < 0).take(limit).collect();
+
+    println!("\n{}", style("⬆️  Biggest Growth:").bold().white());
+    if growth.is_empty() {
+        println!("  {}", style("Nothing grew").dim());
+    }
+    for (path, delta) in growth {
+        println!("  {:<50} {}", style(path).cyan(), style(format_delta(*delta)).red());
+    }
+
+    println!("\n{}", style("⬇️  Biggest Shrinkage:").bold().white());
+    if shrinkage.is_empty() {
+        println!("  {}", style("Nothing shrank").dim());
+    }
+    for (path, delta) in shrinkage {
+        println!("  {:<50} {}", style(path).cyan(), style(format_delta(*delta)).green());
+    }
+
+    Ok(())
+}
+
+fn format_delta(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!("{}{}", sign, crate::utils::format_size(delta.unsigned_abs()))
+}
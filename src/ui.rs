@@ -1,22 +1,8 @@
 use anyhow::Result;
 use console::style;
 use dialoguer::{theme::ColorfulTheme, Select, Confirm};
-use std::path::Path;
-use walkdir::WalkDir;
 
-fn calculate_directory_size(path: &Path) -> Result<u64> {
-    let mut total_size = 0;
-    
-    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
-        if let Ok(metadata) = entry.metadata() {
-            if metadata.is_file() {
-                total_size += metadata.len();
-            }
-        }
-    }
-    
-    Ok(total_size)
-}
+use crate::utils::calculate_directory_size;
 
 pub async fn run_interactive_mode(dry_run: bool, yes: bool) -> Result<()> {
     println!("{}", style("🎯 Interactive Cleanup Mode").bold().magenta());
@@ -34,6 +20,7 @@ pub async fn run_interactive_mode(dry_run: bool, yes: bool) -> Result<()> {
             "🗂️  Clean all caches",
             "🎯 Select specific caches to clean",
             "🐳 Docker cleanup",
+            "📥 Downloads triage",
             "⚙️  Advanced cleanup menu",
             "❌ Exit",
         ];
@@ -68,10 +55,16 @@ pub async fn run_interactive_mode(dry_run: bool, yes: bool) -> Result<()> {
                 crate::cleaners::docker::cleanup_docker(dry_run, yes).await?;
             }
             5 => {
+                // Downloads triage
+                let size = crate::cleaners::downloads::cleanup_downloads(None, dry_run, yes).await?;
+                crate::history::record_cleanup_or_warn("downloads", size, dry_run);
+                show_cleanup_result("Downloads moved to Trash", size);
+            }
+            6 => {
                 // Advanced cleanup menu
                 run_custom_cleanup_menu(dry_run, yes).await?;
             }
-            6 => {
+            7 => {
                 // Exit
                 println!("{}", style("👋 Thanks for using SpaceCleaner!").green());
                 break;
@@ -107,7 +100,20 @@ async fn run_custom_cleanup_menu(dry_run: bool, yes: bool) -> Result<()> {
         "🎭 Playwright cache",
         "🌐 Browser caches",
         "🗑️  System temp files",
+        "🦀 Rust toolchains",
+        "⬢  Node.js versions (nvm, fnm, Volta, asdf)",
+        "🐍 Python environments (pyenv, conda, Poetry, uv, pipx)",
+        "🧶 Yarn, pnpm, Bun and Deno caches",
         "📱 Development caches",
+        "🖥️  System package caches (apt, dnf, pacman, zypper)",
+        "📜 Logs (systemd journal, rotated logs)",
+        "📦 Snap revisions and Flatpak runtimes",
+        "🗑️  Trash, thumbnails and recent files",
+        "🌿 Git repositories (gc, prune, worktrees, LFS)",
+        "💽 VM and emulator images (Vagrant, libvirt, VirtualBox, Android, minikube)",
+        "🏗️  Infrastructure tooling (Terraform, Helm, kubectl, Bazel, ccache)",
+        "❄️  Nix generations and store garbage",
+        "💥 Core dumps and crash reports",
         "🔙 Back to main menu",
     ];
     
@@ -146,17 +152,70 @@ async fn run_custom_cleanup_menu(dry_run: bool, yes: bool) -> Result<()> {
                 show_cleanup_result("Playwright cache", size);
             }
             6 => {
-                let size = crate::cleaners::caches::cleanup_browser_caches(dry_run, yes).await?;
+                let size = crate::cleaners::browsers::cleanup_browser_caches(dry_run, yes).await?;
                 show_cleanup_result("Browser caches", size);
             }
             7 => {
-                let size = crate::cleaners::caches::cleanup_temp_files(dry_run, yes).await?;
+                let size = crate::cleaners::temp::cleanup_temp_files(None, dry_run, yes).await?;
                 show_cleanup_result("System temp files", size);
             }
             8 => {
-                cleanup_development_caches(dry_run, yes).await?;
+                let size = crate::cleaners::rustup::cleanup_rustup_toolchains(dry_run, yes).await?;
+                show_cleanup_result("Rust toolchains", size);
             }
             9 => {
+                let size = crate::cleaners::node::cleanup_node_versions(dry_run, yes).await?;
+                show_cleanup_result("Node.js versions", size);
+            }
+            10 => {
+                let size = crate::cleaners::python::cleanup_python(None, dry_run, yes).await?;
+                show_cleanup_result("Python environments", size);
+            }
+            11 => {
+                let size = crate::cleaners::javascript::cleanup_js_caches(dry_run, yes).await?;
+                show_cleanup_result("JavaScript package manager caches", size);
+            }
+            12 => {
+                cleanup_development_caches(dry_run, yes).await?;
+            }
+            13 => {
+                let size = crate::cleaners::system::cleanup_package_caches(dry_run, yes).await?;
+                show_cleanup_result("System package caches", size);
+            }
+            14 => {
+                let size = crate::cleaners::logs::cleanup_logs(None, dry_run, yes).await?;
+                show_cleanup_result("Logs", size);
+            }
+            15 => {
+                let mut size = crate::cleaners::snap::cleanup_snap_revisions(dry_run, yes).await?;
+                size += crate::cleaners::flatpak::cleanup_flatpak_runtimes(dry_run, yes).await?;
+                show_cleanup_result("Snap and Flatpak leftovers", size);
+            }
+            16 => {
+                let size = crate::cleaners::desktop::cleanup_desktop(None, dry_run, yes).await?;
+                show_cleanup_result("Trash, thumbnails and recent files", size);
+            }
+            17 => {
+                let size = crate::cleaners::git::cleanup_git_repos(dry_run, yes).await?;
+                show_cleanup_result("Git repositories", size);
+            }
+            18 => {
+                let size = crate::cleaners::vm::cleanup_vm_images(dry_run, yes).await?;
+                show_cleanup_result("VM and emulator images", size);
+            }
+            19 => {
+                let size = crate::cleaners::infra::cleanup_infra_caches(None, None, dry_run, yes).await?;
+                show_cleanup_result("Infrastructure tooling caches", size);
+            }
+            20 => {
+                let size = crate::cleaners::nix::cleanup_nix(None, dry_run, yes).await?;
+                show_cleanup_result("Nix store", size);
+            }
+            21 => {
+                let size = crate::cleaners::crashes::cleanup_crash_dumps(None, dry_run, yes).await?;
+                show_cleanup_result("Core dumps and crash reports", size);
+            }
+            22 => {
                 // Back to main menu
                 break;
             }
@@ -183,10 +242,6 @@ async fn cleanup_development_caches(dry_run: bool, yes: bool) -> Result<()> {
     println!("\n{}", style("📱 Development Cache Cleanup").bold().cyan());
     
     let dev_caches = vec![
-        ("Gradle cache", "~/.gradle/caches"),
-        ("Maven cache", "~/.m2/repository"),
-        ("Cargo cache", "~/.cargo/registry"),
-        ("Go module cache", "~/go/pkg/mod"),
         ("Android build cache", "~/.android/build-cache"),
     ];
     
@@ -236,6 +291,26 @@ async fn cleanup_development_caches(dry_run: bool, yes: bool) -> Result<()> {
         }
     }
     
+    // Gradle and Maven are pruned by version rather than wiped
+    println!("\n{} Gradle and Maven caches", style("☕").cyan());
+    let size = crate::cleaners::jvm::cleanup_jvm(dry_run, yes).await?;
+    show_cleanup_result("Gradle and Maven caches", size);
+    
+    // Go's module cache is read-only and its location configurable
+    println!("\n{} Go module and build caches", style("🐹").cyan());
+    let size = crate::cleaners::go::cleanup_go(None, dry_run, yes).await?;
+    show_cleanup_result("Go caches", size);
+    
+    // Editors keep caches for releases, folders and extensions that are long gone
+    println!("\n{} Editor and IDE caches", style("📝").cyan());
+    let size = crate::cleaners::ide::cleanup_ide_caches(dry_run, yes).await?;
+    show_cleanup_result("Editor and IDE caches", size);
+    
+    // Cargo is pruned by last use rather than wiped, so it has its own cleaner
+    println!("\n{} Cargo registry and build artifacts", style("🦀").cyan());
+    let size = crate::cleaners::cargo::cleanup_cargo(None, dry_run, yes).await?;
+    show_cleanup_result("Cargo caches", size);
+    
     Ok(())
 }
 
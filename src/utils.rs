@@ -1,6 +1,235 @@
+use anyhow::Result;
 use byte_unit::{Byte, UnitType};
+use chrono::{DateTime, Local};
+use std::env;
+use std::fs;
+use std::fs::Metadata;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+use walkdir::{DirEntry, WalkDir};
+
+/// Directories that are never worth descending into when searching projects.
+const SKIPPED_PROJECT_DIRS: &[&str] = &[".git", "node_modules", "target", ".venv", "venv"];
+
+/// How deep below each project root to look for repositories, lock files,
+/// version pins and the like.
+const PROJECT_SEARCH_DEPTH: usize = 8;
 
 pub fn format_size(bytes: u64) -> String {
     let byte = Byte::from_u64(bytes);
     byte.get_appropriate_unit(UnitType::Binary).to_string()
-}
\ No newline at end of file
+}
+
+pub fn calculate_directory_size(path: &Path) -> Result<u64> {
+    let mut total_size = 0;
+    
+    for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+        if let Ok(metadata) = entry.metadata() {
+            if metadata.is_file() {
+                total_size += metadata.len();
+            }
+        }
+    }
+    
+    Ok(total_size)
+}
+
+/// Directory for files SpaceCleaner writes between runs (status files, history).
+/// Follows `XDG_STATE_HOME`, falling back to `~/.local/state/spacecleaner`.
+pub fn state_dir() -> PathBuf {
+    state_home().join("spacecleaner")
+}
+
+/// The directory in environment variable `name`, if it is set and not empty.
+pub fn env_dir(name: &str) -> Option<PathBuf> {
+    env::var(name).ok().filter(|dir| !dir.is_empty()).map(PathBuf::from)
+}
+
+/// The per-user cache directory: `~/Library/Caches` on macOS, `XDG_CACHE_HOME`
+/// or `~/.cache` elsewhere.
+pub fn cache_home() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        return PathBuf::from(shellexpand::tilde("~/Library/Caches").as_ref());
+    }
+    env_dir("XDG_CACHE_HOME").unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/.cache").as_ref()))
+}
+
+/// `XDG_CONFIG_HOME` or `~/.config`.
+pub fn config_home() -> PathBuf {
+    env_dir("XDG_CONFIG_HOME").unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/.config").as_ref()))
+}
+
+/// `XDG_DATA_HOME` or `~/.local/share`.
+pub fn data_home() -> PathBuf {
+    env_dir("XDG_DATA_HOME").unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/.local/share").as_ref()))
+}
+
+/// `XDG_STATE_HOME` or `~/.local/state`.
+pub fn state_home() -> PathBuf {
+    env_dir("XDG_STATE_HOME").unwrap_or_else(|| PathBuf::from(shellexpand::tilde("~/.local/state").as_ref()))
+}
+
+/// Where applications keep their settings: `~/Library/Application Support` on
+/// macOS, [`config_home`] elsewhere.
+pub fn app_support_home() -> PathBuf {
+    if cfg!(target_os = "macos") {
+        return PathBuf::from(shellexpand::tilde("~/Library/Application Support").as_ref());
+    }
+    config_home()
+}
+
+/// Walks a project root down to [`PROJECT_SEARCH_DEPTH`], skipping VCS,
+/// dependency and build output directories.
+pub fn walk_project_root(root: &Path) -> impl Iterator<Item = DirEntry> {
+    WalkDir::new(root)
+        .max_depth(PROJECT_SEARCH_DEPTH)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || !entry.file_name().to_str().is_some_and(|name| SKIPPED_PROJECT_DIRS.contains(&name))
+        })
+        .filter_map(|e| e.ok())
+}
+
+/// The later of the access and modification times; atime alone is unreliable on
+/// filesystems mounted with `noatime`.
+pub fn last_used(metadata: &Metadata) -> SystemTime {
+    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    let accessed = metadata.accessed().unwrap_or(SystemTime::UNIX_EPOCH);
+    modified.max(accessed)
+}
+
+/// The most recent use of any file inside `path`. Directories are ignored because
+/// listing them, as this walk does, refreshes their access time.
+pub fn last_used_in(path: &Path) -> SystemTime {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| !entry.file_type().is_dir())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| last_used(&metadata))
+        .max()
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+}
+
+/// Whether `command` is installed and answers `--version`.
+pub fn command_exists(command: &str) -> bool {
+    Command::new(command)
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// The effective UID of the process.
+pub fn effective_uid() -> u32 {
+    // SAFETY: geteuid has no preconditions and cannot fail
+    unsafe { libc::geteuid() }
+}
+
+/// Whether the process runs with an effective UID of 0.
+pub fn is_root() -> bool {
+    effective_uid() == 0
+}
+
+/// A local date and time for listings, or `never` for the epoch that stands in
+/// for an unknown time.
+pub fn format_date(time: SystemTime) -> String {
+    if time == SystemTime::UNIX_EPOCH {
+        return "never".to_string();
+    }
+    DateTime::<Local>::from(time).format("%Y-%m-%d %H:%M").to_string()
+}
+
+/// The subdirectories of `dir`.
+pub fn read_dirs(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// The last component of `path` as a string, empty if there is none.
+pub fn file_name(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().into_owned()
+}
+
+/// Local path of a `file://` URI; `None` for other schemes.
+pub fn file_uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    Some(PathBuf::from(percent_decode(path)))
+}
+
+/// Decodes `%XX` escapes, leaving malformed ones as they are.
+pub fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+            if let Ok(byte) = u8::from_str_radix(hex, 16) {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Numeric components of a dotted version for ordering: `1.10.2` -> `[1, 10, 2]`.
+/// Anything after the first non-numeric component is ignored.
+pub fn version_key(version: &str) -> Vec<u64> {
+    version.split('.').map_while(|part| part.parse().ok()).collect()
+}
+
+pub fn days_ago(days: u64) -> SystemTime {
+    SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60)
+}
+
+/// Removes a file, symlink or directory tree. Read-only directories (such as Go's
+/// module cache) are made writable first when a plain removal is refused.
+pub fn remove_path(path: &Path) -> Result<()> {
+    let metadata = fs::symlink_metadata(path)?;
+    
+    if !metadata.is_dir() {
+        fs::remove_file(path)?;
+        return Ok(());
+    }
+    
+    if fs::remove_dir_all(path).is_err() {
+        for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_type().is_dir() {
+                if let Ok(metadata) = entry.metadata() {
+                    let mode = metadata.permissions().mode() | 0o700;
+                    fs::set_permissions(entry.path(), fs::Permissions::from_mode(mode)).ok();
+                }
+            }
+        }
+        fs::remove_dir_all(path)?;
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_key_orders_numerically() {
+        assert!(version_key("5.10.0") > version_key("5.9.1"));
+        assert_eq!(version_key("1.2.3"), vec![1, 2, 3]);
+        // Pre-release suffixes end the key
+        assert_eq!(version_key("1.2.3-beta1"), vec![1, 2]);
+    }
+}
@@ -0,0 +1,305 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use console::style;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub struct WatchOptions {
+    pub path: PathBuf,
+    pub threshold: f64,
+    pub hysteresis: f64,
+    pub action: String,
+    pub interval: Duration,
+    pub cooldown: Duration,
+    pub status_file: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum WatchState {
+    /// Usage is below the threshold; the next crossing runs the action
+    Armed,
+    /// The action ran and usage has not yet dropped below the re-arm level
+    Triggered,
+}
+
+/// What a single usage check does.
+#[derive(Debug, PartialEq)]
+enum Step {
+    Idle,
+    Rearm,
+    Intervene,
+    RateLimited,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Intervention {
+    time: DateTime<Local>,
+    action: String,
+    dry_run: bool,
+    usage_before: f64,
+    usage_after: f64,
+    freed_bytes: u64,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct WatchStatus {
+    pid: u32,
+    path: PathBuf,
+    state: WatchState,
+    threshold_percent: f64,
+    rearm_percent: f64,
+    action: String,
+    last_check: DateTime<Local>,
+    usage_percent: f64,
+    used_bytes: u64,
+    available_bytes: u64,
+    interventions: u64,
+    last_intervention: Option<Intervention>,
+    next_action_allowed: Option<DateTime<Local>>,
+}
+
+/// Parses a threshold such as `90%`, `90` or `0.9` into a percentage.
+pub fn parse_threshold(value: &str) -> Result<f64> {
+    let trimmed = value.trim().trim_end_matches('%');
+    let number: f64 = trimmed
+        .parse()
+        .with_context(|| format!("Invalid threshold: {}", value))?;
+
+    // Accept fractions like 0.9 as well as percentages
+    let percent = if number > 0.0 && number <= 1.0 && !value.contains('%') {
+        number * 100.0
+    } else {
+        number
+    };
+
+    if percent <= 0.0 || percent >= 100.0 {
+        return Err(anyhow::anyhow!("Threshold must be between 0% and 100%, got {}", value));
+    }
+
+    Ok(percent)
+}
+
+/// Checks that `hysteresis` leaves a re-arm level between 0% and `threshold`.
+pub fn parse_hysteresis(hysteresis: f64, threshold: f64) -> Result<f64> {
+    if !hysteresis.is_finite() || hysteresis < 0.0 || hysteresis >= threshold {
+        return Err(anyhow::anyhow!(
+            "Hysteresis must be between 0 and the {:.1}% threshold, got {}",
+            threshold,
+            hysteresis
+        ));
+    }
+
+    Ok(hysteresis)
+}
+
+pub fn default_status_file() -> PathBuf {
+    crate::utils::state_dir().join("watch-status.json")
+}
+
+pub async fn run_watch(options: WatchOptions, dry_run: bool) -> Result<()> {
+    // Fail early on unknown profiles instead of at the first intervention
+    crate::cleaners::profiles::find_profile(&options.action)?;
+
+    let rearm = options.threshold - options.hysteresis;
+
+    println!("{}", style("👀 Watching disk usage").bold().cyan());
+    println!("  Path:        {}", options.path.display());
+    println!("  Threshold:   {:.1}% (re-arms below {:.1}%)", options.threshold, rearm);
+    println!("  Action:      {}", options.action);
+    println!("  Interval:    {}s, cooldown {}s", options.interval.as_secs(), options.cooldown.as_secs());
+    println!("  Status file: {}", options.status_file.display());
+    if dry_run {
+        println!("{}", style("🔍 DRY RUN MODE - Actions will only report what they would clean").yellow());
+    }
+    println!();
+
+    let mut state = WatchState::Armed;
+    let mut interventions = 0u64;
+    let mut last_intervention: Option<Intervention> = None;
+
+    loop {
+        match check_usage(&options.path) {
+            Ok((percent, used, available)) => {
+                let now = Local::now();
+                let cooldown_until = last_intervention.as_ref().map(|last| {
+                    last.time + chrono::Duration::from_std(options.cooldown).unwrap_or_default()
+                });
+                let cooled_down = cooldown_until.is_none_or(|until| now >= until);
+
+                match next_step(state, percent, options.threshold, rearm, cooled_down) {
+                    Step::Rearm => {
+                        log(&format!("Usage {:.1}% is below {:.1}%, re-arming", percent, rearm));
+                        state = WatchState::Armed;
+                    }
+                    Step::Intervene => {
+                        log(&format!(
+                            "Usage {:.1}% crossed threshold {:.1}%, running '{}' cleanup",
+                            percent, options.threshold, options.action
+                        ));
+                        let intervention = intervene(&options, percent, dry_run).await;
+                        match &intervention.error {
+                            Some(e) => log(&format!("Cleanup '{}' failed: {}", options.action, e)),
+                            None => log(&format!(
+                                "Cleanup '{}' finished: usage {:.1}% -> {:.1}%, freed {}",
+                                options.action,
+                                intervention.usage_before,
+                                intervention.usage_after,
+                                crate::utils::format_size(intervention.freed_bytes)
+                            )),
+                        }
+                        interventions += 1;
+                        last_intervention = Some(intervention);
+                        state = WatchState::Triggered;
+                    }
+                    Step::RateLimited => {
+                        if let Some(until) = cooldown_until {
+                            log(&format!(
+                                "Usage {:.1}% is above threshold but cleanup is rate limited until {}",
+                                percent,
+                                until.format("%Y-%m-%d %H:%M:%S")
+                            ));
+                        }
+                    }
+                    Step::Idle => {}
+                }
+
+                let status = WatchStatus {
+                    pid: std::process::id(),
+                    path: options.path.clone(),
+                    state,
+                    threshold_percent: options.threshold,
+                    rearm_percent: rearm,
+                    action: options.action.clone(),
+                    last_check: now,
+                    usage_percent: percent,
+                    used_bytes: used,
+                    available_bytes: available,
+                    interventions,
+                    next_action_allowed: last_intervention.as_ref().map(|last| {
+                        last.time + chrono::Duration::from_std(options.cooldown).unwrap_or_default()
+                    }),
+                    last_intervention: last_intervention.clone(),
+                };
+
+                if let Err(e) = write_status(&options.status_file, &status) {
+                    log(&format!("Could not write status file: {}", e));
+                }
+            }
+            Err(e) => log(&format!("Could not read filesystem usage: {}", e)),
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(options.interval) => {}
+            _ = tokio::signal::ctrl_c() => {
+                log("Received interrupt, stopping watch");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Only an armed watch acts on a crossing; once triggered it waits until usage
+/// drops below the re-arm level, so usage hovering at the threshold cleans once.
+fn next_step(state: WatchState, percent: f64, threshold: f64, rearm: f64, cooled_down: bool) -> Step {
+    match state {
+        WatchState::Armed if percent >= threshold => {
+            if cooled_down { Step::Intervene } else { Step::RateLimited }
+        }
+        WatchState::Triggered if percent < rearm => Step::Rearm,
+        _ => Step::Idle,
+    }
+}
+
+async fn intervene(options: &WatchOptions, usage_before: f64, dry_run: bool) -> Intervention {
+    let used_before = check_usage(&options.path).map(|(_, used, _)| used).ok();
+
+    // Nobody is around to answer prompts, so the watch runs cleanups unattended
+    let result = crate::cleaners::profiles::run_named_profile(&options.action, dry_run, true).await;
+
+    let (usage_after, used_after) = check_usage(&options.path)
+        .map(|(percent, used, _)| (percent, Some(used)))
+        .unwrap_or((usage_before, None));
+
+    let freed_bytes = match (used_before, used_after) {
+        (Some(before), Some(after)) => before.saturating_sub(after),
+        _ => 0,
+    };
+
+    Intervention {
+        time: Local::now(),
+        action: options.action.clone(),
+        dry_run,
+        usage_before,
+        usage_after,
+        freed_bytes,
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+/// Returns `(usage percent, used bytes, available bytes)` the way `df` reports it.
+fn check_usage(path: &Path) -> Result<(f64, u64, u64)> {
+    let (_total, used, available) = crate::scanner::get_filesystem_usage(path)?;
+    let usable = (used + available).max(1);
+    Ok((used as f64 * 100.0 / usable as f64, used, available))
+}
+
+fn write_status(path: &Path, status: &WatchStatus) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Write to a temp file first so monitoring never reads a half-written status
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, serde_json::to_string_pretty(status)?)?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+fn log(message: &str) {
+    println!("[{}] {}", Local::now().format("%Y-%m-%d %H:%M:%S"), message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn armed_watch_intervenes_at_threshold() {
+        assert_eq!(next_step(WatchState::Armed, 89.9, 90.0, 85.0, true), Step::Idle);
+        assert_eq!(next_step(WatchState::Armed, 90.0, 90.0, 85.0, true), Step::Intervene);
+        assert_eq!(next_step(WatchState::Armed, 95.0, 90.0, 85.0, false), Step::RateLimited);
+    }
+
+    #[test]
+    fn triggered_watch_waits_for_rearm_level() {
+        assert_eq!(next_step(WatchState::Triggered, 95.0, 90.0, 85.0, true), Step::Idle);
+        assert_eq!(next_step(WatchState::Triggered, 87.0, 90.0, 85.0, true), Step::Idle);
+        assert_eq!(next_step(WatchState::Triggered, 85.0, 90.0, 85.0, true), Step::Idle);
+        assert_eq!(next_step(WatchState::Triggered, 84.9, 90.0, 85.0, true), Step::Rearm);
+    }
+
+    #[test]
+    fn parses_thresholds() {
+        assert_eq!(parse_threshold("90%").unwrap(), 90.0);
+        assert_eq!(parse_threshold("90").unwrap(), 90.0);
+        assert_eq!(parse_threshold("0.9").unwrap(), 90.0);
+        assert!(parse_threshold("100%").is_err());
+        assert!(parse_threshold("full").is_err());
+    }
+
+    #[test]
+    fn rejects_hysteresis_outside_threshold() {
+        assert_eq!(parse_hysteresis(5.0, 90.0).unwrap(), 5.0);
+        assert_eq!(parse_hysteresis(0.0, 90.0).unwrap(), 0.0);
+        assert!(parse_hysteresis(-1.0, 90.0).is_err());
+        assert!(parse_hysteresis(90.0, 90.0).is_err());
+        assert!(parse_hysteresis(f64::NAN, 90.0).is_err());
+    }
+}